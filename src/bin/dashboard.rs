@@ -1,5 +1,85 @@
+//! Read-only fund status dashboard.
+//!
+//! Listens on `--bind`, else `BIND_ADDRESS`, else `0.0.0.0:8084`. An invalid
+//! address exits with code 1 before any connection is made.
+//!
+//! Usage: `dashboard [--bind <host:port>]`
+
+const USAGE: &str = "Usage: dashboard [--bind <host:port>]";
+
+/// Returns the `--bind` value, if given.
+fn parse_arguments(arguments: &[String]) -> Result<Option<String>, String> {
+    let mut bind_address: Option<String> = None;
+    let mut index = 0;
+
+    while index < arguments.len() {
+        match arguments[index].as_str() {
+            "--bind" => {
+                index += 1;
+                let value = arguments
+                    .get(index)
+                    .ok_or_else(|| "--bind requires a value".to_string())?;
+                bind_address = Some(value.clone());
+            }
+            other => {
+                return Err(format!("Unknown argument '{}'\n{}", other, USAGE));
+            }
+        }
+        index += 1;
+    }
+
+    Ok(bind_address)
+}
+
 #[tokio::main]
 async fn main() {
     fund::common::crypto::install_default_crypto_provider();
-    fund::dashboard::run().await;
+
+    let raw_arguments: Vec<String> = std::env::args().skip(1).collect();
+    let bind_address = parse_arguments(&raw_arguments).and_then(|cli_bind_address| {
+        let environment_bind_address = std::env::var("BIND_ADDRESS").ok();
+        fund::dashboard::server::resolve_bind_address(
+            cli_bind_address.as_deref(),
+            environment_bind_address.as_deref(),
+        )
+    });
+    let bind_address = match bind_address {
+        Ok(bind_address) => bind_address,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    };
+
+    fund::dashboard::run(bind_address).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_arguments;
+
+    fn arguments(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_arguments_without_bind_returns_none() {
+        assert_eq!(parse_arguments(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_arguments_reads_bind_value() {
+        let parsed = parse_arguments(&arguments(&["--bind", "127.0.0.1:9000"])).unwrap();
+        assert_eq!(parsed.as_deref(), Some("127.0.0.1:9000"));
+    }
+
+    #[test]
+    fn test_parse_arguments_bind_without_value_is_error() {
+        assert!(parse_arguments(&arguments(&["--bind"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_arguments_unknown_argument_is_error() {
+        assert!(parse_arguments(&arguments(&["--port", "8084"])).is_err());
+    }
 }
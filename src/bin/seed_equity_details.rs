@@ -9,13 +9,13 @@
 
 use fund::common::observability::init_tracing;
 use fund::data::database::seed_equity_details;
-use fund::data::equity_details::{embedded_csv, parse_embedded_equity_details};
+use fund::data::equity_details::{
+    parse_embedded_equity_details, upload_details_csv, DETAILS_CSV_KEY,
+};
 use fund::data::state::State;
 
 const USAGE: &str = "Usage: seed_equity_details --target <s3|postgresql|all>";
 
-const S3_KEY: &str = "data/equity/details/details.csv";
-
 #[derive(Debug)]
 enum Target {
     S3,
@@ -58,24 +58,6 @@ fn parse_arguments(arguments: &[String]) -> Result<Target, String> {
     target.ok_or_else(|| format!("--target is required\n{}", USAGE))
 }
 
-/// Upload the embedded equity details CSV to S3 as raw bytes.
-async fn upload_to_s3(state: &State) -> Result<(), String> {
-    use aws_sdk_s3::primitives::ByteStream;
-
-    state
-        .s3_client
-        .put_object()
-        .bucket(&state.bucket_name)
-        .key(S3_KEY)
-        .body(ByteStream::from(embedded_csv().as_bytes().to_vec()))
-        .send()
-        .await
-        .map_err(|error| format!("Failed to upload equity details to S3: {}", error))?;
-
-    tracing::info!(key = S3_KEY, "Uploaded equity details CSV to S3");
-    Ok(())
-}
-
 /// Parse the embedded equity details CSV and insert the rows into PostgreSQL.
 async fn insert_into_postgresql(state: &State) -> Result<u64, String> {
     let pool = state
@@ -83,7 +65,7 @@ async fn insert_into_postgresql(state: &State) -> Result<u64, String> {
         .pool()
         .ok_or("PostgreSQL not configured but target is postgresql")?;
 
-    let details = parse_embedded_equity_details()
+    let details = parse_embedded_equity_details(&state.ticker_denylist)
         .map_err(|error| format!("Failed to parse equity details CSV: {}", error))?;
 
     let rows_affected = seed_equity_details(pool, &details)
@@ -115,20 +97,20 @@ async fn main() {
     let state = State::from_env().await;
 
     let result: Result<(), String> = match target {
-        Target::S3 => upload_to_s3(&state).await.map(|()| {
-            println!("Equity details uploaded to S3: {}", S3_KEY);
+        Target::S3 => upload_details_csv(&state).await.map(|()| {
+            println!("Equity details uploaded to S3: {}", DETAILS_CSV_KEY);
         }),
         Target::PostgreSQL => insert_into_postgresql(&state).await.map(|rows| {
             println!("Equity details seeded to PostgreSQL: {} rows", rows);
         }),
         Target::All => {
             let postgresql_result = insert_into_postgresql(&state).await;
-            let s3_result = upload_to_s3(&state).await;
+            let s3_result = upload_details_csv(&state).await;
             match (&postgresql_result, &s3_result) {
                 (Ok(rows), Ok(())) => {
                     println!(
                         "Equity details seeded to PostgreSQL ({} rows) and S3 ({})",
-                        rows, S3_KEY
+                        rows, DETAILS_CSV_KEY
                     );
                     Ok(())
                 }
@@ -10,3 +10,5 @@ pub mod events;
 #[cfg(any(feature = "data", feature = "portfolio"))]
 pub mod market_hours;
 pub mod observability;
+#[cfg(test)]
+pub(crate) mod test_env;
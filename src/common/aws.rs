@@ -10,6 +10,22 @@ pub async fn s3_client() -> aws_sdk_s3::Client {
     aws_sdk_s3::Client::new(&load_config().await)
 }
 
+/// Rebuild `client` so each request is retried up to `max_retries` times
+/// after the first attempt. The SDK's standard retry mode backs off
+/// exponentially with jitter and only retries transient failures (timeouts,
+/// throttling, 5xx), so a single flaky `PutObject` no longer fails a sync.
+pub fn with_max_retries(client: &aws_sdk_s3::Client, max_retries: u32) -> aws_sdk_s3::Client {
+    let retry_config =
+        aws_config::retry::RetryConfig::standard().with_max_attempts(max_retries.saturating_add(1));
+    aws_sdk_s3::Client::from_conf(
+        client
+            .config()
+            .to_builder()
+            .retry_config(retry_config)
+            .build(),
+    )
+}
+
 /// Build the Hive-partitioned S3 key for one day of parquet data, e.g.
 /// `data/equity/bars/year=2026/month=06/day=10/data.parquet`. The single
 /// source of truth for the date-partition layout: the data manager's daily
@@ -28,7 +44,76 @@ pub fn date_partitioned_key(prefix: &str, date: chrono::NaiveDate) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::date_partitioned_key;
+    use super::{date_partitioned_key, with_max_retries};
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+    use aws_sdk_s3::primitives::ByteStream;
+
+    fn mock_s3_client(endpoint_url: &str) -> aws_sdk_s3::Client {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "tests"))
+            .endpoint_url(endpoint_url)
+            .force_path_style(true)
+            .build();
+        aws_sdk_s3::Client::from_conf(config)
+    }
+
+    #[tokio::test]
+    async fn test_with_max_retries_retries_put_after_transient_failures() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("PUT", "/bucket/key.parquet")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let succeeding = server
+            .mock("PUT", "/bucket/key.parquet")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = with_max_retries(&mock_s3_client(&server.url()), 3);
+
+        client
+            .put_object()
+            .bucket("bucket")
+            .key("key.parquet")
+            .body(ByteStream::from(b"data".to_vec()))
+            .send()
+            .await
+            .unwrap();
+
+        failing.assert_async().await;
+        succeeding.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_with_zero_max_retries_returns_first_failure() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("PUT", "/bucket/key.parquet")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = with_max_retries(&mock_s3_client(&server.url()), 0);
+
+        let result = client
+            .put_object()
+            .bucket("bucket")
+            .key("key.parquet")
+            .body(ByteStream::from(b"data".to_vec()))
+            .send()
+            .await;
+
+        assert!(result.is_err());
+        failing.assert_async().await;
+    }
 
     #[test]
     fn test_date_partitioned_key_zero_pads_month_and_day() {
@@ -31,6 +31,7 @@ pub async fn connect_optional_pool() -> (Option<PgPool>, bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::test_env::EnvVarRestoreGuard;
 
     fn make_runtime() -> tokio::runtime::Runtime {
         tokio::runtime::Builder::new_current_thread()
@@ -39,36 +40,6 @@ mod tests {
             .unwrap()
     }
 
-    /// RAII guard that restores a single environment variable on drop.
-    ///
-    /// Guarantees cleanup even when the test body panics. Tests using this guard
-    /// must be marked `#[serial_test::serial]` to prevent concurrent env access.
-    struct EnvVarRestoreGuard {
-        key: &'static str,
-        previous: Option<String>,
-    }
-
-    impl EnvVarRestoreGuard {
-        fn save(key: &'static str) -> Self {
-            Self {
-                key,
-                previous: std::env::var(key).ok(),
-            }
-        }
-    }
-
-    impl Drop for EnvVarRestoreGuard {
-        fn drop(&mut self) {
-            // SAFETY: Protected by #[serial_test::serial] — no concurrent env access.
-            unsafe {
-                match self.previous.as_ref() {
-                    Some(value) => std::env::set_var(self.key, value),
-                    None => std::env::remove_var(self.key),
-                }
-            }
-        }
-    }
-
     #[test]
     #[serial_test::serial]
     fn test_connect_optional_pool_returns_false_when_database_url_unset() {
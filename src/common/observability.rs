@@ -5,13 +5,37 @@ use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
+/// Stdout log format selected by `LOG_FORMAT`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogFormat {
+    Json,
+    Pretty,
+}
+
+impl LogFormat {
+    /// Parses `LOG_FORMAT` (`json` or `pretty`, case-insensitive). Unset means
+    /// JSON. An unknown value falls back to JSON with a warning on stderr,
+    /// since tracing is not yet running to report it.
+    fn from_value(value: Option<&str>) -> Self {
+        match value.map(|raw| raw.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("json") => Self::Json,
+            Some("pretty") => Self::Pretty,
+            Some(other) => {
+                eprintln!("Unknown LOG_FORMAT '{other}', using json");
+                Self::Json
+            }
+        }
+    }
+}
+
 /// Initialize structured JSON tracing for a service.
 ///
 /// The `service` parameter identifies which service is emitting logs
 /// (e.g. `"data"`, `"inference"`, `"portfolio"`) and is included in the
 /// initial `Tracing initialized` log line for correlation.
 ///
-/// Logs to stdout at the `RUST_LOG` level (default `info`). When the log
+/// Logs to stdout at the `RUST_LOG` level (default `info`), as JSON unless
+/// `LOG_FORMAT=pretty` selects human-readable lines for local runs. When the log
 /// directory is writable, also logs to a rolling daily file there; the
 /// directory is `FUND_LOG_DIR` when set, otherwise `/var/log/fund` (local
 /// dev sets `FUND_LOG_DIR` to a writable path via devenv). When `file_filter`
@@ -36,7 +60,12 @@ pub fn init_tracing(
 ) -> Option<WorkerGuard> {
     let fund_profile = env::var("FUND_PROFILE").unwrap_or_else(|_| "unknown".to_string());
 
-    let stdout_layer = tracing_subscriber::fmt::layer().json().with_target(true);
+    // Exactly one of these is `Some`; the file layer is always JSON.
+    let log_format = LogFormat::from_value(env::var("LOG_FORMAT").ok().as_deref());
+    let stdout_json_layer = (log_format == LogFormat::Json)
+        .then(|| tracing_subscriber::fmt::layer().json().with_target(true));
+    let stdout_pretty_layer = (log_format == LogFormat::Pretty)
+        .then(|| tracing_subscriber::fmt::layer().with_target(true));
     let global_filter = || {
         tracing_subscriber::EnvFilter::try_from_default_env()
             .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))
@@ -65,7 +94,8 @@ pub fn init_tracing(
             // never attaches, so handing back the guard would mislead callers.
             let initialized = tracing_subscriber::registry()
                 .with(global_filter())
-                .with(stdout_layer)
+                .with(stdout_json_layer)
+                .with(stdout_pretty_layer)
                 .with(file_layer)
                 .try_init()
                 .is_ok();
@@ -79,7 +109,8 @@ pub fn init_tracing(
             eprintln!("File logging disabled: {error}");
             tracing_subscriber::registry()
                 .with(global_filter())
-                .with(stdout_layer)
+                .with(stdout_json_layer)
+                .with(stdout_pretty_layer)
                 .try_init()
                 .ok();
             None
@@ -148,39 +179,11 @@ pub fn init_tracing_file_only(log_file: &str, service: &str) -> Option<WorkerGua
 
 #[cfg(test)]
 mod tests {
-    use super::init_tracing;
+    use super::{init_tracing, LogFormat};
+    use crate::common::test_env::EnvVarRestoreGuard;
     use serial_test::serial;
     use std::env;
 
-    /// RAII guard that restores an environment variable on drop, panic-safe.
-    ///
-    /// Tests using this must be marked `#[serial]` to prevent concurrent env access.
-    struct EnvVarRestoreGuard {
-        key: &'static str,
-        previous: Option<String>,
-    }
-
-    impl EnvVarRestoreGuard {
-        fn save(key: &'static str) -> Self {
-            Self {
-                key,
-                previous: env::var(key).ok(),
-            }
-        }
-    }
-
-    impl Drop for EnvVarRestoreGuard {
-        fn drop(&mut self) {
-            // SAFETY: Protected by #[serial_test::serial] — no concurrent env access.
-            unsafe {
-                match self.previous.as_ref() {
-                    Some(value) => env::set_var(self.key, value),
-                    None => env::remove_var(self.key),
-                }
-            }
-        }
-    }
-
     #[test]
     #[serial]
     fn test_init_tracing_is_idempotent() {
@@ -225,4 +228,21 @@ mod tests {
         unsafe { env::remove_var("FUND_PROFILE") };
         let _tracing_guard = init_tracing("test-no-profile-observability.log", None, "test");
     }
+
+    #[test]
+    fn test_log_format_defaults_to_json_and_accepts_pretty() {
+        assert_eq!(LogFormat::from_value(None), LogFormat::Json);
+        assert_eq!(LogFormat::from_value(Some("JSON")), LogFormat::Json);
+        assert_eq!(LogFormat::from_value(Some("pretty")), LogFormat::Pretty);
+        assert_eq!(LogFormat::from_value(Some("xml")), LogFormat::Json);
+    }
+
+    #[test]
+    #[serial]
+    fn test_init_tracing_with_pretty_log_format_does_not_panic() {
+        let _restore = EnvVarRestoreGuard::save("LOG_FORMAT");
+        // SAFETY: Protected by #[serial_test::serial] — no concurrent env access.
+        unsafe { env::set_var("LOG_FORMAT", "pretty") };
+        let _tracing_guard = init_tracing("test-pretty-observability.log", Some("warn"), "test");
+    }
 }
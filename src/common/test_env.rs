@@ -0,0 +1,44 @@
+//! Panic-safe environment variable overrides for unit tests.
+
+/// RAII guard that restores a single environment variable on drop.
+///
+/// Guarantees cleanup even when the test body panics. Tests using this guard
+/// must be marked `#[serial_test::serial]` to prevent concurrent env access.
+pub(crate) struct EnvVarRestoreGuard {
+    key: &'static str,
+    previous: Option<String>,
+}
+
+impl EnvVarRestoreGuard {
+    pub(crate) fn save(key: &'static str) -> Self {
+        Self {
+            key,
+            previous: std::env::var(key).ok(),
+        }
+    }
+
+    /// Saves `key`, then sets it to `value`, or removes it when `None`.
+    pub(crate) fn set(key: &'static str, value: Option<&str>) -> Self {
+        let guard = Self::save(key);
+        set_env(key, value);
+        guard
+    }
+}
+
+impl Drop for EnvVarRestoreGuard {
+    fn drop(&mut self) {
+        set_env(self.key, self.previous.as_deref());
+    }
+}
+
+/// Sets `key` to `value`, or removes it when `None`. Callers hold an
+/// [`EnvVarRestoreGuard`] for `key` so the original value comes back.
+pub(crate) fn set_env(key: &str, value: Option<&str>) {
+    // SAFETY: Protected by #[serial_test::serial] — no concurrent env access.
+    unsafe {
+        match value {
+            Some(value) => std::env::set_var(key, value),
+            None => std::env::remove_var(key),
+        }
+    }
+}
@@ -34,10 +34,10 @@ use crate::common::observability::init_tracing_file_only;
 const POOL_MAX_CONNECTIONS: u32 = 4;
 
 /// Initializes tracing, connects to the read-only database, spawns background
-/// tasks, and starts the HTTP server.
+/// tasks, and starts the HTTP server on `bind_address`.
 ///
 /// Panics on startup if `DATABASE_URL` is unset or the database is unreachable.
-pub async fn run() {
+pub async fn run(bind_address: std::net::SocketAddr) {
     let _tracing_guard = init_tracing_file_only("dashboard-service.log", "dashboard");
     info!("Starting dashboard service");
 
@@ -55,5 +55,5 @@ pub async fn run() {
     cache::spawn_polling_task(state.clone(), pool.clone());
     cache::spawn_event_listener_task(state.clone(), pool);
 
-    server::run_server(state).await;
+    server::run_server(state, bind_address).await;
 }
@@ -362,7 +362,7 @@ async fn fetch_latest_bars_inserted_at(
     let row = sqlx::query("SELECT MAX(inserted_at) AS max_inserted_at FROM equity_bars")
         .fetch_one(pool)
         .await?;
-    Ok(row.try_get("max_inserted_at")?)
+    row.try_get("max_inserted_at")
 }
 
 /// Returns the most recent `completed_at` across all rebalance sessions.
@@ -376,7 +376,7 @@ async fn fetch_last_rebalance_completed_at(
         sqlx::query("SELECT MAX(completed_at) AS max_completed_at FROM equity_rebalance_sessions")
             .fetch_one(pool)
             .await?;
-    Ok(row.try_get("max_completed_at")?)
+    row.try_get("max_completed_at")
 }
 
 /// Returns the first snapshot (newest-to-oldest order) whose timestamp is at or
@@ -265,7 +265,7 @@ fn render_predictions_section(state: &DashboardState) -> String {
         values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
         values
     };
-    let median_q50 = if q50_values.len() % 2 == 0 {
+    let median_q50 = if q50_values.len().is_multiple_of(2) {
         let mid = q50_values.len() / 2;
         (q50_values[mid - 1] + q50_values[mid]) / 2.0
     } else {
@@ -538,16 +538,20 @@ mod tests {
 
     #[test]
     fn test_render_html_with_updated_timestamp() {
-        let mut state = DashboardState::default();
-        state.last_updated = Some(Utc::now());
+        let state = DashboardState {
+            last_updated: Some(Utc::now()),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Last updated:"));
     }
 
     #[test]
     fn test_render_html_with_database_error() {
-        let mut state = DashboardState::default();
-        state.database_error = Some("connection refused".to_string());
+        let state = DashboardState {
+            database_error: Some("connection refused".to_string()),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Database error"));
         assert!(!html.contains("connection refused"));
@@ -555,9 +559,11 @@ mod tests {
 
     #[test]
     fn test_render_html_database_error_does_not_render_raw_details() {
-        let mut state = DashboardState::default();
-        state.last_updated = Some(Utc::now());
-        state.database_error = Some("<script>alert(1)</script>".to_string());
+        let state = DashboardState {
+            last_updated: Some(Utc::now()),
+            database_error: Some("<script>alert(1)</script>".to_string()),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Database error (last ok:"));
         assert!(!html.contains("<script>"));
@@ -573,20 +579,22 @@ mod tests {
 
     #[test]
     fn test_render_html_with_positions() {
-        let mut state = DashboardState::default();
-        state.open_positions = vec![OpenPosition {
-            pair_id: PairID::parse("AAPL-MSFT").unwrap(),
-            long_ticker: Ticker::new("AAPL").unwrap(),
-            short_ticker: Ticker::new("MSFT").unwrap(),
-            z_score: Decimal::new(15, 1),
-            hedge_ratio: Decimal::ONE,
-            signal_strength: Decimal::new(8, 1),
-            long_dollar_amount: Decimal::new(10000, 0),
-            short_dollar_amount: Decimal::new(9500, 0),
-            opened_at: Utc::now(),
-        }];
-        state.gross_exposure = Decimal::new(19500, 0);
-        state.net_exposure = Decimal::new(500, 0);
+        let state = DashboardState {
+            open_positions: vec![OpenPosition {
+                pair_id: PairID::parse("AAPL-MSFT").unwrap(),
+                long_ticker: Ticker::new("AAPL").unwrap(),
+                short_ticker: Ticker::new("MSFT").unwrap(),
+                z_score: Decimal::new(15, 1),
+                hedge_ratio: Decimal::ONE,
+                signal_strength: Decimal::new(8, 1),
+                long_dollar_amount: Decimal::new(10000, 0),
+                short_dollar_amount: Decimal::new(9500, 0),
+                opened_at: Utc::now(),
+            }],
+            gross_exposure: Decimal::new(19500, 0),
+            net_exposure: Decimal::new(500, 0),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("AAPL"));
         assert!(html.contains("MSFT"));
@@ -596,39 +604,43 @@ mod tests {
 
     #[test]
     fn test_render_html_plural_pairs() {
-        let mut state = DashboardState::default();
-        state.open_positions = vec![
-            OpenPosition {
-                pair_id: PairID::parse("AAPL-MSFT").unwrap(),
-                long_ticker: Ticker::new("AAPL").unwrap(),
-                short_ticker: Ticker::new("MSFT").unwrap(),
-                z_score: Decimal::ONE,
-                hedge_ratio: Decimal::ONE,
-                signal_strength: Decimal::ONE,
-                long_dollar_amount: Decimal::new(10000, 0),
-                short_dollar_amount: Decimal::new(9500, 0),
-                opened_at: Utc::now(),
-            },
-            OpenPosition {
-                pair_id: PairID::parse("TSLA-NVDA").unwrap(),
-                long_ticker: Ticker::new("TSLA").unwrap(),
-                short_ticker: Ticker::new("NVDA").unwrap(),
-                z_score: Decimal::ONE,
-                hedge_ratio: Decimal::ONE,
-                signal_strength: Decimal::ONE,
-                long_dollar_amount: Decimal::new(8000, 0),
-                short_dollar_amount: Decimal::new(7500, 0),
-                opened_at: Utc::now(),
-            },
-        ];
+        let state = DashboardState {
+            open_positions: vec![
+                OpenPosition {
+                    pair_id: PairID::parse("AAPL-MSFT").unwrap(),
+                    long_ticker: Ticker::new("AAPL").unwrap(),
+                    short_ticker: Ticker::new("MSFT").unwrap(),
+                    z_score: Decimal::ONE,
+                    hedge_ratio: Decimal::ONE,
+                    signal_strength: Decimal::ONE,
+                    long_dollar_amount: Decimal::new(10000, 0),
+                    short_dollar_amount: Decimal::new(9500, 0),
+                    opened_at: Utc::now(),
+                },
+                OpenPosition {
+                    pair_id: PairID::parse("TSLA-NVDA").unwrap(),
+                    long_ticker: Ticker::new("TSLA").unwrap(),
+                    short_ticker: Ticker::new("NVDA").unwrap(),
+                    z_score: Decimal::ONE,
+                    hedge_ratio: Decimal::ONE,
+                    signal_strength: Decimal::ONE,
+                    long_dollar_amount: Decimal::new(8000, 0),
+                    short_dollar_amount: Decimal::new(7500, 0),
+                    opened_at: Utc::now(),
+                },
+            ],
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("2 pairs open"));
     }
 
     #[test]
     fn test_render_html_with_rebalance_age() {
-        let mut state = DashboardState::default();
-        state.last_rebalance_completed_at = Some(Utc::now() - Duration::minutes(5));
+        let state = DashboardState {
+            last_rebalance_completed_at: Some(Utc::now() - Duration::minutes(5)),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Rebalance:"));
         assert!(html.contains("fresh"));
@@ -648,10 +660,12 @@ mod tests {
 
     #[test]
     fn test_render_html_performance_with_values() {
-        let mut state = DashboardState::default();
-        state.period_returns = PeriodReturns {
-            fund_one_day: Some(10.0),
-            spy_one_day: Some(1.1),
+        let state = DashboardState {
+            period_returns: PeriodReturns {
+                fund_one_day: Some(10.0),
+                spy_one_day: Some(1.1),
+                ..Default::default()
+            },
             ..Default::default()
         };
         let html = render_html(&state);
@@ -668,24 +682,26 @@ mod tests {
 
     #[test]
     fn test_render_html_with_closed_trade_summary() {
-        let mut state = DashboardState::default();
-        state.closed_trades = vec![ClosedTrade {
-            pair_id: PairID::parse("AAPL-MSFT").unwrap(),
-            long_ticker: Ticker::new("AAPL").unwrap(),
-            short_ticker: Ticker::new("MSFT").unwrap(),
-            realized_profit_and_loss: Some(Decimal::new(500, 0)),
-            return_percent: Some(Decimal::new(2, 0)),
-            holding_seconds: Some(3600),
-            close_reason: Some(CloseReason::ProfitTaken),
-            closed_at: Some(Utc::now()),
-        }];
-        state.closed_trades_summary = ClosedTradesSummary {
-            total_closed: 1,
-            win_rate: Some(1.0),
-            profit_factor: None,
-            average_return_percent: Some(2.0),
-            average_holding_seconds: Some(3600.0),
-            total_realized_profit_and_loss: Some(Decimal::new(500, 0)),
+        let state = DashboardState {
+            closed_trades: vec![ClosedTrade {
+                pair_id: PairID::parse("AAPL-MSFT").unwrap(),
+                long_ticker: Ticker::new("AAPL").unwrap(),
+                short_ticker: Ticker::new("MSFT").unwrap(),
+                realized_profit_and_loss: Some(Decimal::new(500, 0)),
+                return_percent: Some(Decimal::new(2, 0)),
+                holding_seconds: Some(3600),
+                close_reason: Some(CloseReason::ProfitTaken),
+                closed_at: Some(Utc::now()),
+            }],
+            closed_trades_summary: ClosedTradesSummary {
+                total_closed: 1,
+                win_rate: Some(1.0),
+                profit_factor: None,
+                average_return_percent: Some(2.0),
+                average_holding_seconds: Some(3600.0),
+                total_realized_profit_and_loss: Some(Decimal::new(500, 0)),
+            },
+            ..Default::default()
         };
         let html = render_html(&state);
         assert!(html.contains("$500.00"));
@@ -706,35 +722,37 @@ mod tests {
 
     #[test]
     fn test_render_html_with_predictions_summary() {
-        let mut state = DashboardState::default();
-        state.predictions = vec![
-            PredictionRow {
-                ticker: Ticker::new("AAPL").unwrap(),
-                quantile_10: -0.001,
-                quantile_50: 0.002,
-                quantile_90: 0.005,
-                model_run_id: "run-abc123".to_string(),
-                timestamp: Utc::now() - Duration::hours(2),
-            },
-            PredictionRow {
-                ticker: Ticker::new("MSFT").unwrap(),
-                quantile_10: -0.003,
-                quantile_50: -0.001,
-                quantile_90: 0.002,
-                model_run_id: "run-abc123".to_string(),
-                timestamp: Utc::now() - Duration::hours(2),
-            },
-        ];
-        state.model_run_information = Some(
-            ModelRunInformation::new(
-                Utc::now() - Duration::hours(2),
-                Some(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
-                Some(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
-                Some(0.123),
-                Some(0.725),
-            )
-            .unwrap(),
-        );
+        let state = DashboardState {
+            predictions: vec![
+                PredictionRow {
+                    ticker: Ticker::new("AAPL").unwrap(),
+                    quantile_10: -0.001,
+                    quantile_50: 0.002,
+                    quantile_90: 0.005,
+                    model_run_id: "run-abc123".to_string(),
+                    timestamp: Utc::now() - Duration::hours(2),
+                },
+                PredictionRow {
+                    ticker: Ticker::new("MSFT").unwrap(),
+                    quantile_10: -0.003,
+                    quantile_50: -0.001,
+                    quantile_90: 0.002,
+                    model_run_id: "run-abc123".to_string(),
+                    timestamp: Utc::now() - Duration::hours(2),
+                },
+            ],
+            model_run_information: Some(
+                ModelRunInformation::new(
+                    Utc::now() - Duration::hours(2),
+                    Some(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+                    Some(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+                    Some(0.123),
+                    Some(0.725),
+                )
+                .unwrap(),
+            ),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Tickers"));
         assert!(html.contains(">2<"));
@@ -747,8 +765,10 @@ mod tests {
 
     #[test]
     fn test_render_html_with_bars_freshness() {
-        let mut state = DashboardState::default();
-        state.latest_bars_inserted_at = Some(Utc::now() - Duration::hours(3));
+        let state = DashboardState {
+            latest_bars_inserted_at: Some(Utc::now() - Duration::hours(3)),
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Bars:"));
     }
@@ -929,15 +949,17 @@ mod tests {
     #[test]
     fn test_performance_snapshot_not_needed_for_html() {
         // Verify performance section renders even with empty history
-        let mut state = DashboardState::default();
-        state.performance_history = vec![PerformanceSnapshot {
-            snapshot_timestamp: Utc::now(),
-            net_asset_value: Decimal::new(100000, 0),
-            gross_return: None,
-            net_return: None,
-            total_slippage_cost: Decimal::ZERO,
-            spy_close: None,
-        }];
+        let state = DashboardState {
+            performance_history: vec![PerformanceSnapshot {
+                snapshot_timestamp: Utc::now(),
+                net_asset_value: Decimal::new(100000, 0),
+                gross_return: None,
+                net_return: None,
+                total_slippage_cost: Decimal::ZERO,
+                spy_close: None,
+            }],
+            ..Default::default()
+        };
         let html = render_html(&state);
         assert!(html.contains("Performance"));
     }
@@ -1,41 +1,91 @@
 //! Axum HTTP server for the dashboard service.
 //!
 //! Serves a single HTML page at `/` that renders the full dashboard state,
-//! plus a `/health` endpoint for liveness checks. The page auto-refreshes
-//! every 30 seconds via a `<meta>` tag matching the background poll interval.
+//! plus a `/health` endpoint for liveness checks and a `/ready` endpoint for
+//! readiness checks. The page auto-refreshes every 30 seconds via a `<meta>`
+//! tag matching the background poll interval.
 
 use axum::extract::State;
-use axum::response::Html;
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse};
 use axum::routing::get;
-use axum::Router;
-use tracing::info;
+use axum::{Json, Router};
+use std::net::SocketAddr;
+use tracing::{info, warn};
 
 use crate::dashboard::cache::SharedState;
 use crate::dashboard::html::render_html;
 
-/// Port the dashboard HTTP server listens on.
-const PORT: u16 = 8084;
-
-/// Starts the Axum HTTP server on [`PORT`].
+/// Address the dashboard listens on when neither `--bind` nor
+/// `BIND_ADDRESS` is set.
 ///
 /// Binds to all interfaces so the service is reachable via the exe.dev HTTP
-/// proxy. Runs until the process is terminated.
-pub async fn run_server(state: SharedState) {
-    let application = Router::new()
-        .route("/", get(render_dashboard))
-        .route("/health", get(|| async { "ok" }))
-        .with_state(state);
+/// proxy.
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:8084";
+
+/// Resolves the listen address. A `--bind` value takes precedence over
+/// `BIND_ADDRESS`, which takes precedence over [`DEFAULT_BIND_ADDRESS`].
+pub fn resolve_bind_address(
+    cli_bind_address: Option<&str>,
+    environment_bind_address: Option<&str>,
+) -> Result<SocketAddr, String> {
+    let raw = cli_bind_address
+        .or(environment_bind_address)
+        .unwrap_or(DEFAULT_BIND_ADDRESS);
+    raw.trim()
+        .parse::<SocketAddr>()
+        .map_err(|error| format!("Invalid bind address '{}': {}", raw, error))
+}
+
+/// Starts the Axum HTTP server on `bind_address`. Runs until the process is
+/// terminated.
+pub async fn run_server(state: SharedState, bind_address: SocketAddr) {
+    let application = router(state);
 
-    let listener = tokio::net::TcpListener::bind(("0.0.0.0", PORT))
+    let listener = tokio::net::TcpListener::bind(bind_address)
         .await
-        .unwrap_or_else(|error| panic!("Failed to bind port {PORT}: {error}"));
+        .unwrap_or_else(|error| panic!("Failed to bind {bind_address}: {error}"));
 
-    info!("Dashboard server listening on port {PORT}");
+    info!(address = %bind_address, "Dashboard server listening");
     axum::serve(listener, application)
         .await
         .unwrap_or_else(|error| panic!("Server error: {error}"));
 }
 
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/", get(render_dashboard))
+        .route("/health", get(|| async { "ok" }))
+        .route("/ready", get(readiness))
+        .with_state(state)
+}
+
+/// Handles `GET /ready`: 200 once a poll has succeeded and the latest poll
+/// did not fail, otherwise 503. Handlers never query Postgres directly, so
+/// readiness reflects the cache the page is rendered from. Like the page, the
+/// body never carries the raw database error; it is logged instead.
+async fn readiness(State(state): State<SharedState>) -> impl IntoResponse {
+    let dashboard = state.read().await;
+    let database = match (&dashboard.database_error, dashboard.last_updated) {
+        (Some(error), _) => {
+            warn!(error = %error, "Dashboard not ready because the database poll failed");
+            Err("unavailable")
+        }
+        (None, None) => Err("no successful poll yet"),
+        (None, Some(_)) => Ok(()),
+    };
+    match database {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "ready": true, "database": "ok" })),
+        ),
+        Err(error) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "ready": false, "database": error })),
+        ),
+    }
+}
+
 /// Handles `GET /`: reads cached dashboard state and renders the full HTML page.
 async fn render_dashboard(State(state): State<SharedState>) -> Html<String> {
     let dashboard = state.read().await;
@@ -52,11 +102,50 @@ mod tests {
     use tower::ServiceExt;
 
     fn build_router() -> Router {
-        let state: SharedState = Arc::new(RwLock::new(DashboardState::default()));
-        Router::new()
-            .route("/", get(render_dashboard))
-            .route("/health", get(|| async { "ok" }))
-            .with_state(state)
+        router(Arc::new(RwLock::new(DashboardState::default())))
+    }
+
+    async fn ready_response(dashboard: DashboardState) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .uri("/ready")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = router(Arc::new(RwLock::new(dashboard)))
+            .oneshot(request)
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_resolve_bind_address_defaults_when_unset() {
+        let address = resolve_bind_address(None, None).unwrap();
+        assert_eq!(address, DEFAULT_BIND_ADDRESS.parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_bind_address_uses_environment_value() {
+        let address = resolve_bind_address(None, Some("127.0.0.1:9000")).unwrap();
+        assert_eq!(address, "127.0.0.1:9000".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_bind_address_cli_overrides_environment() {
+        let address = resolve_bind_address(Some("127.0.0.1:7000"), Some("127.0.0.1:9000")).unwrap();
+        assert_eq!(address, "127.0.0.1:7000".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_bind_address_rejects_invalid_value() {
+        let error = resolve_bind_address(Some("localhost"), None).unwrap_err();
+        assert!(
+            error.contains("Invalid bind address 'localhost'"),
+            "{error}"
+        );
     }
 
     #[tokio::test]
@@ -100,4 +189,35 @@ mod tests {
         assert!(html.contains("<!DOCTYPE html>"));
         assert!(html.contains("OSCM"));
     }
+
+    #[tokio::test]
+    async fn test_ready_endpoint_after_successful_poll() {
+        let dashboard = DashboardState {
+            last_updated: Some(chrono::Utc::now()),
+            ..DashboardState::default()
+        };
+        let (status, body) = ready_response(dashboard).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+    }
+
+    #[tokio::test]
+    async fn test_ready_endpoint_before_first_poll() {
+        let (status, body) = ready_response(DashboardState::default()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["database"], "no successful poll yet");
+    }
+
+    #[tokio::test]
+    async fn test_ready_endpoint_reports_database_error() {
+        let dashboard = DashboardState {
+            last_updated: Some(chrono::Utc::now()),
+            database_error: Some("connection refused".to_string()),
+            ..DashboardState::default()
+        };
+        let (status, body) = ready_response(dashboard).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["database"], "unavailable");
+    }
 }
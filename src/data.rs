@@ -9,7 +9,9 @@ pub mod errors;
 pub mod export;
 pub mod manifest;
 pub mod market_calendar;
+pub mod massive;
 pub mod scheduler;
 pub mod state;
+pub mod ticker_denylist;
 pub mod types;
 pub mod validation;
@@ -1,7 +1,11 @@
 use crate::data::database;
+use crate::data::massive::send_with_retry;
 use crate::data::state::State;
 use crate::data::types::{create_equity_bar_dataframe, EquityBar, TradingDate};
 use crate::domain::market::Ticker;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::operation::head_object::HeadObjectError;
 use aws_sdk_s3::primitives::ByteStream;
 use chrono::{DateTime, NaiveDate, Utc};
 use polars::prelude::{ParquetReader, ParquetWriter, SerReader};
@@ -83,39 +87,101 @@ fn parse_equity_bar(result: &EquityBarResult, inserted_at: DateTime<Utc>) -> Opt
 /// This must stay byte-for-byte aligned with the tide training reader so that
 /// synced and backfilled files are read uniformly from one prefix; an earlier
 /// stray `daily/` segment here diverged and hid backfilled data from training.
-fn equity_bars_key(date: NaiveDate) -> String {
+pub fn equity_bars_key(date: NaiveDate) -> String {
     crate::common::aws::date_partitioned_key("data/equity/bars", date)
 }
 
+/// Clock skew allowed before a bar counts as future-dated.
+const FUTURE_TIMESTAMP_TOLERANCE_SECONDS: i64 = 300;
+
+/// Rejects the batch when any bar is stamped later than `now` plus
+/// [`FUTURE_TIMESTAMP_TOLERANCE_SECONDS`]. Future-dated rows sort ahead of
+/// real data and would be picked up by "latest" lookups.
+fn ensure_no_future_timestamps(bars: &[EquityBar], now: DateTime<Utc>) -> Result<(), String> {
+    let limit = now + chrono::Duration::seconds(FUTURE_TIMESTAMP_TOLERANCE_SECONDS);
+    let future: Vec<DateTime<Utc>> = bars
+        .iter()
+        .map(EquityBar::timestamp)
+        .filter(|timestamp| *timestamp > limit)
+        .collect();
+    match future.iter().max() {
+        Some(latest) => Err(format!(
+            "Rejected {} equity bars timestamped in the future (latest {})",
+            future.len(),
+            latest
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Refuses to replace an existing partition with an empty day unless
+/// `allow_empty_overwrite` is set, so a transient empty or fully filtered
+/// result cannot wipe out stored bars.
+async fn ensure_empty_write_is_allowed(state: &State, key: &str) -> Result<(), String> {
+    if state.allow_empty_overwrite {
+        return Ok(());
+    }
+    match state
+        .s3_client
+        .head_object()
+        .bucket(&state.bucket_name)
+        .key(key)
+        .send()
+        .await
+    {
+        Ok(_) => Err(format!(
+            "Refused to overwrite {} with zero equity bars; set ALLOW_EMPTY_OVERWRITE=true to force",
+            key
+        )),
+        Err(error) if matches!(error.as_service_error(), Some(HeadObjectError::NotFound(_))) => {
+            Ok(())
+        }
+        Err(error) => Err(format!(
+            "Failed to check existing S3 object {}: {}",
+            key,
+            DisplayErrorContext(&error)
+        )),
+    }
+}
+
 async fn write_equity_bars_to_s3(
     state: &State,
     trading_date: &TradingDate,
     bars: &[EquityBar],
 ) -> Result<(), String> {
+    let key = equity_bars_key(trading_date.as_naive_date());
+
+    if bars.is_empty() {
+        ensure_empty_write_is_allowed(state, &key).await?;
+    }
+
     let mut dataframe = create_equity_bar_dataframe(bars)
-        .map_err(|error| format!("Failed to create DataFrame: {}", error))?;
+        .map_err(|error| format!("Failed to create DataFrame for {}: {}", key, error))?;
 
     crate::data::validation::validate_equity_bars_or_reject(
         &dataframe,
         trading_date.as_naive_date(),
-    )?;
+    )
+    .map_err(|error| format!("Rejected equity bars for {}: {}", key, error))?;
 
     let mut buffer = Vec::new();
     ParquetWriter::new(&mut buffer)
+        .with_compression(state.parquet_compression)
         .finish(&mut dataframe)
-        .map_err(|error| format!("Failed to serialize Parquet: {}", error))?;
+        .map_err(|error| format!("Failed to serialize Parquet for {}: {}", key, error))?;
 
-    let key = equity_bars_key(trading_date.as_naive_date());
+    state.ensure_bucket_accessible().await?;
 
     state
         .s3_client
         .put_object()
         .bucket(&state.bucket_name)
         .key(&key)
+        .set_storage_class(state.storage_class.clone())
         .body(ByteStream::from(buffer))
         .send()
         .await
-        .map_err(|error| format!("Failed to upload to S3: {}", error))?;
+        .map_err(|error| format!("Failed to upload to S3 {}: {}", key, error))?;
 
     info!(key = key, "Wrote equity bars Parquet to S3");
     Ok(())
@@ -144,33 +210,52 @@ async fn fetch_equity_bars_for_date(
     let date_str = trading_date.as_naive_date().format("%Y-%m-%d").to_string();
     let url = grouped_bars_url(&state.massive.base, &date_str);
 
+    state.massive_circuit_breaker.check()?;
     info!("Sending request to Massive API");
-    let response = state
+    let request_started = std::time::Instant::now();
+    let request = state
         .http_client
         .get(&url)
         .header("accept", "application/json")
-        .query(&[("adjusted", "true"), ("apiKey", massive_api_key.as_str())])
-        .send()
-        .await
-        .map_err(|err| {
-            warn!(
-                "Failed to send request to Massive API: {}",
-                err.without_url()
-            );
-            "Failed to send API request".to_string()
-        })?;
+        .query(&[("adjusted", "true"), ("apiKey", massive_api_key.as_str())]);
+    let response = send_with_retry(
+        &state.massive_rate_limiter,
+        request,
+        state.massive_max_attempts,
+    )
+    .await
+    .map_err(|err| {
+        state.massive_circuit_breaker.record_failure();
+        warn!(
+            elapsed_ms = request_started.elapsed().as_millis() as u64,
+            "Failed to send request to Massive API: {}",
+            err.without_url()
+        );
+        "Failed to send API request".to_string()
+    })?;
 
-    info!(status = %response.status(), "Received response from Massive API");
+    state
+        .massive_circuit_breaker
+        .record_status(response.status());
+    info!(
+        status = %response.status(),
+        elapsed_ms = request_started.elapsed().as_millis() as u64,
+        "Received response from Massive API"
+    );
 
     let text_content = response
         .error_for_status()
         .map_err(|err| {
+            let status = err.status();
             warn!(
                 "API request failed with status code {:?}: {}",
-                err.status(),
+                status,
                 err.without_url()
             );
-            "API request failed".to_string()
+            match status {
+                Some(status) => format!("Massive returned {}", status.as_u16()),
+                None => "API request failed".to_string(),
+            }
         })?
         .text()
         .await
@@ -193,19 +278,14 @@ async fn fetch_equity_bars_for_date(
         "Massive API results received"
     );
 
-    let Some(results) = massive_response.results else {
-        warn!("No results field in API response");
+    let Some(results) = results_from_response(massive_response)? else {
         return Ok(None);
     };
 
-    if results.is_empty() {
-        return Ok(None);
-    }
-
     let raw_count = results.len();
     let inserted_at = Utc::now();
 
-    let equity_bars: Vec<EquityBar> = results
+    let mut equity_bars: Vec<EquityBar> = results
         .iter()
         .filter_map(|result| parse_equity_bar(result, inserted_at))
         .collect();
@@ -216,9 +296,49 @@ async fn fetch_equity_bars_for_date(
         "Converted results to valid equity bars"
     );
 
+    let denied = state
+        .ticker_denylist
+        .retain_allowed(&mut equity_bars, EquityBar::ticker);
+    if denied > 0 {
+        info!(rows = denied, "Dropped equity bars for denylisted tickers");
+    }
+
+    if state.reject_future_timestamps {
+        ensure_no_future_timestamps(&equity_bars, inserted_at)?;
+    }
+
     Ok(Some(equity_bars))
 }
 
+/// Separates a genuinely empty trading day from a malformed response.
+///
+/// `resultsCount: 0` with no (or an empty) `results` array is an empty day. A
+/// missing `results` array while `resultsCount` is positive means the upstream
+/// response was truncated or malformed, so it is an error rather than a
+/// silently empty day.
+fn results_from_response(
+    massive_response: MassiveResponse,
+) -> Result<Option<Vec<EquityBarResult>>, String> {
+    match massive_response.results {
+        Some(results) if results.is_empty() => Ok(None),
+        Some(results) => Ok(Some(results)),
+        None if massive_response.results_count > 0 => {
+            warn!(
+                rows = massive_response.results_count,
+                "Massive response reported results but had no results field"
+            );
+            Err(format!(
+                "Massive response reported {} results but had no results field",
+                massive_response.results_count
+            ))
+        }
+        None => {
+            debug!("No results field in API response");
+            Ok(None)
+        }
+    }
+}
+
 /// Fetch a day's grouped-daily bars and persist them to PostgreSQL (when a pool
 /// is configured) and S3. Used by the on-demand `sync` handler.
 pub async fn fetch_and_store_equity_bars(
@@ -252,6 +372,8 @@ pub struct SeedSummary {
     pub days_skipped_non_trading: usize,
     pub days_failed: usize,
     pub total_bars: usize,
+    /// Error for each failed day, in date order.
+    pub failures: Vec<(NaiveDate, String)>,
 }
 
 /// Where equity bar data is read from.
@@ -274,6 +396,22 @@ pub enum SeedTarget {
     All,
 }
 
+/// Parquet files begin and end with these four magic bytes.
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// Rejects objects that are not Parquet (for example a CSV uploaded to a
+/// Parquet key) with a clear message instead of an opaque decoder error.
+fn ensure_parquet_magic(bytes: &[u8], key: &str) -> Result<(), String> {
+    let is_parquet = bytes.len() >= 2 * PARQUET_MAGIC.len()
+        && bytes.starts_with(PARQUET_MAGIC)
+        && bytes.ends_with(PARQUET_MAGIC);
+    if is_parquet {
+        Ok(())
+    } else {
+        Err(format!("not a valid parquet file: {}", key))
+    }
+}
+
 /// Read one day's equity bar Parquet from S3 and parse rows into validated
 /// `EquityBar` values. Uses the same `Ticker::new` boundary validation as the
 /// Massive API ingest path so both sources produce identical domain objects.
@@ -286,21 +424,25 @@ async fn read_equity_bars_from_s3(
     let response = match state
         .s3_client
         .get_object()
-        .bucket(&state.bucket_name)
+        .bucket(&state.read_bucket_name)
         .key(&key)
         .send()
         .await
     {
         Ok(response) => response,
         Err(error) => {
-            let error_string = error.to_string();
-            if error_string.contains("NoSuchKey") || error_string.contains("not found") {
-                debug!("No S3 object for {}: {}", key, error_string);
+            let is_missing = matches!(error.as_service_error(), Some(GetObjectError::NoSuchKey(_)))
+                || error
+                    .raw_response()
+                    .is_some_and(|response| response.status().as_u16() == 404);
+            if is_missing {
+                debug!("No S3 object for {}", key);
                 return Ok(None);
             }
             return Err(format!(
                 "Failed to read S3 object {}: {}",
-                key, error_string
+                key,
+                DisplayErrorContext(&error)
             ));
         }
     };
@@ -312,6 +454,8 @@ async fn read_equity_bars_from_s3(
         .map_err(|error| format!("Failed to read S3 body for {}: {}", key, error))?
         .into_bytes();
 
+    ensure_parquet_magic(&bytes, &key)?;
+
     let dataframe = ParquetReader::new(Cursor::new(bytes))
         .finish()
         .map_err(|error| format!("Failed to parse Parquet from {}: {}", key, error))?;
@@ -425,6 +569,22 @@ async fn read_equity_bars_from_s3(
         );
     }
 
+    let denied = state
+        .ticker_denylist
+        .retain_allowed(&mut bars, EquityBar::ticker);
+    if denied > 0 {
+        info!(
+            rows = denied,
+            key = key,
+            "Dropped equity bars for denylisted tickers"
+        );
+    }
+
+    if state.reject_future_timestamps {
+        ensure_no_future_timestamps(&bars, inserted_at)
+            .map_err(|error| format!("{} in {}", error, key))?;
+    }
+
     if bars.is_empty() {
         Ok(None)
     } else {
@@ -519,6 +679,7 @@ pub async fn seed(
                     Err(error) => {
                         summary.days_failed += 1;
                         warn!(date = %date.format("%Y-%m-%d"), error = %error, "Failed to seed equity bars");
+                        summary.failures.push((date, error));
                     }
                 }
             }
@@ -542,7 +703,11 @@ pub async fn seed(
 
 #[cfg(test)]
 mod tests {
-    use super::{equity_bars_key, grouped_bars_url, parse_equity_bar, EquityBarResult};
+    use super::{
+        ensure_no_future_timestamps, ensure_parquet_magic, equity_bars_key, grouped_bars_url,
+        parse_equity_bar, read_equity_bars_from_s3, results_from_response, EquityBarResult,
+        MassiveResponse,
+    };
     use chrono::{DateTime, NaiveDate, Utc};
 
     #[test]
@@ -762,6 +927,7 @@ mod tests {
         assert_eq!(summary.days_skipped_non_trading, 0);
         assert_eq!(summary.days_failed, 0);
         assert_eq!(summary.total_bars, 0);
+        assert!(summary.failures.is_empty());
     }
 
     #[test]
@@ -865,10 +1031,100 @@ mod tests {
             days_skipped_non_trading: 2,
             days_failed: 1,
             total_bars: 1000,
+            failures: Vec::new(),
         };
         let debug_str = format!("{:?}", summary);
         assert!(debug_str.contains("SeedSummary"));
         assert!(debug_str.contains("days_processed"));
         assert!(debug_str.contains("total_bars"));
     }
+
+    #[test]
+    fn test_ensure_parquet_magic_accepts_parquet_bytes() {
+        let mut dataframe = polars::df!("ticker" => ["AAPL"]).unwrap();
+        let mut buffer = Vec::new();
+        polars::prelude::ParquetWriter::new(&mut buffer)
+            .finish(&mut dataframe)
+            .unwrap();
+        assert!(ensure_parquet_magic(&buffer, "data.parquet").is_ok());
+    }
+
+    #[test]
+    fn test_ensure_parquet_magic_rejects_csv_with_key_in_error() {
+        let key = "data/equity/bars/year=2025/month=01/day=03/data.parquet";
+        let error = ensure_parquet_magic(b"ticker,close_price\nAAPL,1.0\n", key).unwrap_err();
+        assert_eq!(error, format!("not a valid parquet file: {}", key));
+    }
+
+    #[test]
+    fn test_ensure_parquet_magic_rejects_truncated_object() {
+        assert!(ensure_parquet_magic(b"PAR1", "data.parquet").is_err());
+        assert!(ensure_parquet_magic(b"PAR1 partial write", "data.parquet").is_err());
+    }
+
+    #[test]
+    fn test_results_from_response_zero_count_without_results_is_empty_day() {
+        let response: MassiveResponse =
+            serde_json::from_str(r#"{"resultsCount": 0, "status": "OK"}"#).unwrap();
+        assert!(results_from_response(response).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_results_from_response_empty_results_is_empty_day() {
+        let response: MassiveResponse =
+            serde_json::from_str(r#"{"resultsCount": 0, "results": []}"#).unwrap();
+        assert!(results_from_response(response).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_results_from_response_positive_count_without_results_is_error() {
+        let response: MassiveResponse =
+            serde_json::from_str(r#"{"resultsCount": 5, "status": "OK"}"#).unwrap();
+        let error = results_from_response(response).unwrap_err();
+        assert!(error.contains("reported 5 results"), "{error}");
+    }
+
+    fn bar_at(timestamp: DateTime<Utc>) -> crate::data::types::EquityBar {
+        parse_equity_bar(
+            &EquityBarResult {
+                t: timestamp.timestamp_millis() as u64,
+                ..make_valid_result()
+            },
+            Utc::now(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ensure_no_future_timestamps_accepts_past_and_skewed_bars() {
+        let now = Utc::now();
+        let bars = vec![
+            bar_at(now - chrono::Duration::days(1)),
+            bar_at(now + chrono::Duration::seconds(60)),
+        ];
+        assert!(ensure_no_future_timestamps(&bars, now).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_no_future_timestamps_rejects_clearly_future_bar() {
+        let now = Utc::now();
+        let bars = vec![
+            bar_at(now - chrono::Duration::days(1)),
+            bar_at(now + chrono::Duration::days(30)),
+        ];
+        let error = ensure_no_future_timestamps(&bars, now).unwrap_err();
+        assert!(error.contains("Rejected 1 equity bars"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_read_equity_bars_from_s3_failure_names_key() {
+        let state = crate::data::state::unreachable_s3_state();
+
+        let date = NaiveDate::from_ymd_opt(2026, 6, 5).unwrap();
+        let error = read_equity_bars_from_s3(&state, date).await.unwrap_err();
+        assert!(
+            error.contains("data/equity/bars/year=2026/month=06/day=05/data.parquet"),
+            "{error}"
+        );
+    }
 }
@@ -1,6 +1,17 @@
 use crate::data::errors::Error;
+use crate::data::state::State;
+use crate::data::ticker_denylist::TickerDenylist;
 use crate::domain::market::{EquityDetail, Ticker};
-use tracing::{info, warn};
+use aws_sdk_s3::primitives::ByteStream;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// S3 key of the live equity details CSV read by the model trainer.
+pub const DETAILS_CSV_KEY: &str = "data/equity/details/details.csv";
+
+/// Staging key written before the live CSV is replaced.
+const DETAILS_CSV_STAGING_KEY: &str = "data/equity/details/details.csv.staging";
 
 /// Equity details CSV embedded at compile time.
 ///
@@ -13,6 +24,325 @@ pub fn embedded_csv() -> &'static str {
     EQUITY_DETAILS_CSV
 }
 
+/// Letter case applied to sector and industry values in the published CSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextCase {
+    /// `CONSUMER ELECTRONICS`, matching what PostgreSQL stores.
+    Upper,
+    /// `Consumer Electronics/Appliances`. A word starts after any character
+    /// other than a letter, digit, or apostrophe, so `Oil/Gas`, `E&P`, and
+    /// `Semi-Conductors` keep each part capitalized. Words of two to four
+    /// letters that are already fully uppercase (`EDP`, `REIT`, `IT`) are kept
+    /// as acronyms.
+    Title,
+}
+
+impl TextCase {
+    /// Parses `upper` or `title` (case-insensitive).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "upper" => Some(Self::Upper),
+            "title" => Some(Self::Title),
+            _ => None,
+        }
+    }
+
+    fn apply(self, value: &str) -> String {
+        match self {
+            Self::Upper => value.to_uppercase(),
+            Self::Title => {
+                let mut titled = String::with_capacity(value.len());
+                let mut word = String::new();
+                for character in value.chars() {
+                    if character.is_alphanumeric() || character == '\'' {
+                        word.push(character);
+                    } else {
+                        push_title_case_word(&mut titled, &word);
+                        word.clear();
+                        titled.push(character);
+                    }
+                }
+                push_title_case_word(&mut titled, &word);
+                titled
+            }
+        }
+    }
+}
+
+fn push_title_case_word(output: &mut String, word: &str) {
+    let is_acronym = (2..=4).contains(&word.chars().count())
+        && word.chars().all(|character| character.is_ascii_uppercase());
+    if is_acronym {
+        output.push_str(word);
+        return;
+    }
+    let mut characters = word.chars();
+    if let Some(first) = characters.next() {
+        output.extend(first.to_uppercase());
+        output.extend(characters.flat_map(char::to_lowercase));
+    }
+}
+
+/// Sector and industry letter case for the equity details CSV published to S3,
+/// configured independently via `SECTOR_CASE` and `INDUSTRY_CASE` (`upper` or
+/// `title`).
+///
+/// PostgreSQL always stores uppercase values regardless of this setting:
+/// portfolio consolidation groups pairs on the exact sector string, so mixing
+/// cases across syncs would split one sector into two. Tickers are always
+/// uppercase (enforced by [`Ticker::new`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetailCase {
+    pub sector: TextCase,
+    pub industry: TextCase,
+}
+
+impl DetailCase {
+    /// Reads `SECTOR_CASE` and `INDUSTRY_CASE`.
+    ///
+    /// Returns `None` when neither is set, in which case the embedded CSV is
+    /// published verbatim. When only one is set the other defaults to
+    /// [`TextCase::Upper`]. A present-but-unrecognized value is a hard error so
+    /// a misconfigured environment fails fast at startup rather than silently
+    /// falling back.
+    pub fn from_env() -> Result<Option<Self>, Error> {
+        let sector = text_case_from_env("SECTOR_CASE")?;
+        let industry = text_case_from_env("INDUSTRY_CASE")?;
+        match (sector, industry) {
+            (None, None) => Ok(None),
+            (sector, industry) => Ok(Some(Self {
+                sector: sector.unwrap_or(TextCase::Upper),
+                industry: industry.unwrap_or(TextCase::Upper),
+            })),
+        }
+    }
+}
+
+fn text_case_from_env(key: &str) -> Result<Option<TextCase>, Error> {
+    match std::env::var(key) {
+        Ok(raw) => TextCase::parse(&raw).map(Some).ok_or_else(|| {
+            let message = format!("{key} must be 'upper' or 'title', got '{raw}'");
+            Error::Other(message)
+        }),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(Error::Other(format!("{key} must be valid UTF-8")))
+        }
+    }
+}
+
+/// Returns the equity details CSV to upload to S3.
+///
+/// Without a [`DetailCase`] or denylisted tickers this is the embedded CSV
+/// unchanged. Otherwise the sector and industry columns are rewritten in the
+/// configured case and rows for denylisted tickers are removed; every other
+/// column and row (including rows with invalid tickers) is kept as it is.
+pub fn published_csv(
+    case: Option<DetailCase>,
+    denylist: &TickerDenylist,
+) -> Result<Cow<'static, str>, Error> {
+    let csv = match case {
+        None => Cow::Borrowed(EQUITY_DETAILS_CSV),
+        Some(case) => Cow::Owned(apply_detail_case(EQUITY_DETAILS_CSV, case)?),
+    };
+    if denylist.is_empty() {
+        return Ok(csv);
+    }
+    remove_denied_rows(&csv, denylist).map(Cow::Owned)
+}
+
+/// Uploads the published CSV to [`DETAILS_CSV_KEY`] via a staging object.
+///
+/// The CSV is first written to a staging key and only copied over the live
+/// key once that upload succeeds, so a failed write can never leave a
+/// truncated or partial live CSV behind. Errors say whether the live object
+/// was touched. Removing the staging object afterwards is best-effort.
+pub async fn upload_details_csv(state: &State) -> Result<(), String> {
+    let csv = published_csv(state.detail_case, &state.ticker_denylist)
+        .map_err(|error| format!("Failed to prepare equity details CSV: {}", error))?;
+
+    state
+        .ensure_bucket_accessible()
+        .await
+        .map_err(|error| format!("{}; live CSV {} is unchanged", error, DETAILS_CSV_KEY))?;
+
+    state
+        .s3_client
+        .put_object()
+        .bucket(&state.bucket_name)
+        .key(DETAILS_CSV_STAGING_KEY)
+        .body(ByteStream::from(csv.as_bytes().to_vec()))
+        .send()
+        .await
+        .map_err(|error| {
+            format!(
+                "Failed to stage equity details CSV at {}; live CSV {} is unchanged: {}",
+                DETAILS_CSV_STAGING_KEY, DETAILS_CSV_KEY, error
+            )
+        })?;
+
+    state
+        .s3_client
+        .copy_object()
+        .bucket(&state.bucket_name)
+        .copy_source(format!("{}/{}", state.bucket_name, DETAILS_CSV_STAGING_KEY))
+        .key(DETAILS_CSV_KEY)
+        .send()
+        .await
+        .map_err(|error| {
+            format!(
+                "Failed to promote equity details CSV to {}; live CSV is unchanged: {}",
+                DETAILS_CSV_KEY, error
+            )
+        })?;
+
+    if let Err(error) = state
+        .s3_client
+        .delete_object()
+        .bucket(&state.bucket_name)
+        .key(DETAILS_CSV_STAGING_KEY)
+        .send()
+        .await
+    {
+        warn!(
+            key = DETAILS_CSV_STAGING_KEY,
+            error = %error,
+            "Failed to remove staged equity details CSV"
+        );
+    }
+
+    info!(key = DETAILS_CSV_KEY, "Uploaded equity details CSV to S3");
+    Ok(())
+}
+
+/// Returns `true` when the live CSV at [`DETAILS_CSV_KEY`] was written less
+/// than `max_age` ago.
+///
+/// A missing object, a failed `HeadObject`, or a missing `LastModified` all
+/// count as stale so the caller falls back to syncing.
+pub async fn details_csv_is_fresh(state: &State, max_age: Duration) -> bool {
+    match state
+        .s3_client
+        .head_object()
+        .bucket(&state.bucket_name)
+        .key(DETAILS_CSV_KEY)
+        .send()
+        .await
+    {
+        Ok(output) => match output.last_modified() {
+            Some(last_modified) => is_younger_than(
+                last_modified.secs(),
+                chrono::Utc::now().timestamp(),
+                max_age,
+            ),
+            None => false,
+        },
+        Err(error) => {
+            debug!(key = DETAILS_CSV_KEY, error = %error, "Could not read equity details CSV age");
+            false
+        }
+    }
+}
+
+fn is_younger_than(written_epoch_seconds: i64, now_epoch_seconds: i64, max_age: Duration) -> bool {
+    let age_seconds = now_epoch_seconds.saturating_sub(written_epoch_seconds);
+    age_seconds >= 0 && (age_seconds as u64) < max_age.as_secs()
+}
+
+fn apply_detail_case(csv_content: &str, case: DetailCase) -> Result<String, Error> {
+    let mut lines = csv_content.lines();
+
+    let header_line = match lines.next() {
+        Some(line) => line,
+        None => return Ok(String::new()),
+    };
+
+    let headers: Vec<&str> = header_line.split(',').map(|h| h.trim()).collect();
+
+    for column in &["sector", "industry"] {
+        if !headers.iter().any(|h| h == column) {
+            let message = format!("CSV missing required column: {}", column);
+            return Err(Error::Other(message));
+        }
+    }
+
+    let sector_index = headers.iter().position(|h| *h == "sector").unwrap();
+    let industry_index = headers.iter().position(|h| *h == "industry").unwrap();
+
+    let mut output = String::with_capacity(csv_content.len());
+    output.push_str(header_line);
+    output.push('\n');
+
+    for line in lines {
+        if line.trim().is_empty() {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let mut fields: Vec<String> = line.split(',').map(str::to_string).collect();
+        if fields.len() != headers.len() {
+            let message = format!(
+                "Malformed CSV row: expected {} fields, got {}",
+                headers.len(),
+                fields.len()
+            );
+            return Err(Error::Other(message));
+        }
+
+        fields[sector_index] = case.sector.apply(&fields[sector_index]);
+        fields[industry_index] = case.industry.apply(&fields[industry_index]);
+
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn remove_denied_rows(csv_content: &str, denylist: &TickerDenylist) -> Result<String, Error> {
+    let mut lines = csv_content.lines();
+
+    let header_line = match lines.next() {
+        Some(line) => line,
+        None => return Ok(String::new()),
+    };
+
+    let Some(ticker_index) = header_line.split(',').position(|h| h.trim() == "ticker") else {
+        return Err(Error::Other(
+            "CSV missing required column: ticker".to_string(),
+        ));
+    };
+
+    let mut output = String::with_capacity(csv_content.len());
+    output.push_str(header_line);
+    output.push('\n');
+
+    let mut denied_rows: usize = 0;
+    for line in lines {
+        let denied = line
+            .split(',')
+            .nth(ticker_index)
+            .and_then(Ticker::new)
+            .is_some_and(|ticker| denylist.is_denied(&ticker));
+        if denied {
+            denied_rows += 1;
+            continue;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    if denied_rows > 0 {
+        info!(
+            rows = denied_rows,
+            "Removed denylisted tickers from published equity details CSV"
+        );
+    }
+
+    Ok(output)
+}
+
 fn parse_equity_details_csv(csv_content: &str) -> Result<Vec<EquityDetail>, Error> {
     let mut lines = csv_content.lines();
 
@@ -84,9 +414,19 @@ fn parse_equity_details_csv(csv_content: &str) -> Result<Vec<EquityDetail>, Erro
     Ok(details)
 }
 
-/// Parses the compile-time-embedded equity details CSV.
-pub fn parse_embedded_equity_details() -> Result<Vec<EquityDetail>, Error> {
-    let details = parse_equity_details_csv(EQUITY_DETAILS_CSV)?;
+/// Parses the compile-time-embedded equity details CSV, dropping denylisted
+/// tickers.
+pub fn parse_embedded_equity_details(
+    denylist: &TickerDenylist,
+) -> Result<Vec<EquityDetail>, Error> {
+    let mut details = parse_equity_details_csv(EQUITY_DETAILS_CSV)?;
+    let denied = denylist.retain_allowed(&mut details, EquityDetail::ticker);
+    if denied > 0 {
+        info!(
+            rows = denied,
+            "Dropped equity details for denylisted tickers"
+        );
+    }
     info!(
         rows = details.len(),
         "Parsed equity details from embedded CSV"
@@ -96,7 +436,13 @@ pub fn parse_embedded_equity_details() -> Result<Vec<EquityDetail>, Error> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_equity_details_csv;
+    use super::{
+        apply_detail_case, is_younger_than, parse_equity_details_csv, published_csv,
+        remove_denied_rows, upload_details_csv, DetailCase, TextCase, EQUITY_DETAILS_CSV,
+    };
+    use crate::common::test_env::EnvVarRestoreGuard;
+    use crate::data::ticker_denylist::TickerDenylist;
+    use serial_test::serial;
 
     #[test]
     fn test_parse_equity_details_csv_valid() {
@@ -282,4 +628,227 @@ mod tests {
         assert_eq!(details[0].sector(), "TECHNOLOGY");
         assert_eq!(details[0].industry(), "NOT AVAILABLE");
     }
+
+    /// Guards `SECTOR_CASE` and `INDUSTRY_CASE` and sets them to the given values.
+    fn set_case_env(sector: Option<&str>, industry: Option<&str>) -> [EnvVarRestoreGuard; 2] {
+        [
+            EnvVarRestoreGuard::set("SECTOR_CASE", sector),
+            EnvVarRestoreGuard::set("INDUSTRY_CASE", industry),
+        ]
+    }
+
+    #[test]
+    fn test_text_case_parse_accepts_known_values() {
+        assert_eq!(TextCase::parse("upper"), Some(TextCase::Upper));
+        assert_eq!(TextCase::parse(" Title "), Some(TextCase::Title));
+        assert_eq!(TextCase::parse("lower"), None);
+    }
+
+    #[test]
+    fn test_title_case_capitalizes_after_punctuation() {
+        assert_eq!(
+            TextCase::Title.apply("oil/gas TRANSMISSION"),
+            "Oil/Gas Transmission"
+        );
+        assert_eq!(TextCase::Title.apply("semi-conductors"), "Semi-Conductors");
+        assert_eq!(TextCase::Title.apply("E&P"), "E&P");
+        assert_eq!(
+            TextCase::Title.apply("beverages (production/distribution)"),
+            "Beverages (Production/Distribution)"
+        );
+        assert_eq!(TextCase::Title.apply("women's apparel"), "Women's Apparel");
+    }
+
+    #[test]
+    fn test_title_case_keeps_short_uppercase_acronyms() {
+        assert_eq!(TextCase::Title.apply("EDP Services"), "EDP Services");
+        assert_eq!(TextCase::Title.apply("IT Services"), "IT Services");
+        assert_eq!(TextCase::Title.apply("REIT"), "REIT");
+        assert_eq!(
+            TextCase::Title.apply("RETAIL: Building Materials"),
+            "Retail: Building Materials"
+        );
+    }
+
+    #[test]
+    fn test_apply_detail_case_title_sector_keeps_ticker_uppercase() {
+        let csv = "ticker,name,sector,industry\nAAPL,Apple Inc.,TECHNOLOGY,Consumer Electronics\n";
+        let case = DetailCase {
+            sector: TextCase::Title,
+            industry: TextCase::Upper,
+        };
+        let published = apply_detail_case(csv, case).unwrap();
+        assert_eq!(
+            published,
+            "ticker,name,sector,industry\nAAPL,Apple Inc.,Technology,CONSUMER ELECTRONICS\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_detail_case_keeps_blank_values_blank() {
+        let csv = "ticker,sector,industry\nAAPL,,\n";
+        let case = DetailCase {
+            sector: TextCase::Title,
+            industry: TextCase::Title,
+        };
+        assert_eq!(apply_detail_case(csv, case).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_apply_detail_case_rejects_malformed_row() {
+        let csv = "ticker,sector,industry\nAAPL,Technology\n";
+        let case = DetailCase {
+            sector: TextCase::Title,
+            industry: TextCase::Title,
+        };
+        assert!(apply_detail_case(csv, case).is_err());
+    }
+
+    #[test]
+    fn test_published_csv_without_case_is_embedded_csv() {
+        let denylist = TickerDenylist::default();
+        assert_eq!(published_csv(None, &denylist).unwrap(), EQUITY_DETAILS_CSV);
+    }
+
+    #[test]
+    fn test_embedded_csv_rows_are_sorted_by_ticker() {
+        // The published CSV keeps embedded row order, so a sorted source keeps
+        // every upload byte-identical for the same data and diffs readable.
+        let tickers: Vec<&str> = EQUITY_DETAILS_CSV
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split(',').next())
+            .collect();
+        for pair in tickers.windows(2) {
+            assert!(
+                pair[0] < pair[1],
+                "{} must sort before {} in data/equity_details.csv",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_published_csv_with_case_keeps_row_count() {
+        let case = DetailCase {
+            sector: TextCase::Title,
+            industry: TextCase::Title,
+        };
+        let published = published_csv(Some(case), &TickerDenylist::default()).unwrap();
+        assert_eq!(
+            published.lines().count(),
+            EQUITY_DETAILS_CSV.lines().count()
+        );
+    }
+
+    #[test]
+    fn test_remove_denied_rows_drops_only_denied_tickers() {
+        let csv = "ticker,sector,industry\nAAPL,Technology,Hardware\nMSFT,Technology,Software\n";
+        let denylist = TickerDenylist::parse("aapl\n").unwrap();
+        assert_eq!(
+            remove_denied_rows(csv, &denylist).unwrap(),
+            "ticker,sector,industry\nMSFT,Technology,Software\n"
+        );
+    }
+
+    #[test]
+    fn test_published_csv_with_denylist_omits_denied_ticker() {
+        let denylist = TickerDenylist::parse("AAPL\n").unwrap();
+        let published = published_csv(None, &denylist).unwrap();
+        assert!(EQUITY_DETAILS_CSV
+            .lines()
+            .any(|line| line.starts_with("AAPL,")));
+        assert!(!published.lines().any(|line| line.starts_with("AAPL,")));
+        assert_eq!(
+            published.lines().count(),
+            EQUITY_DETAILS_CSV.lines().count() - 1
+        );
+    }
+
+    #[test]
+    fn test_denied_ticker_is_absent_after_reparsing_published_csv() {
+        let denylist = TickerDenylist::parse("AAPL\n").unwrap();
+        let published = published_csv(None, &denylist).unwrap();
+        let details = parse_equity_details_csv(&published).unwrap();
+        assert!(!details.iter().any(|detail| detail.ticker() == "AAPL"));
+    }
+
+    #[test]
+    fn test_stored_sector_is_uppercase_regardless_of_published_case() {
+        let csv = "ticker,sector,industry\naapl,Technology,Consumer Electronics\n";
+        let case = DetailCase {
+            sector: TextCase::Title,
+            industry: TextCase::Title,
+        };
+        let published = apply_detail_case(csv, case).unwrap();
+        let details = parse_equity_details_csv(&published).unwrap();
+        assert_eq!(details[0].ticker(), "AAPL");
+        assert_eq!(details[0].sector(), "TECHNOLOGY");
+        assert_eq!(details[0].industry(), "CONSUMER ELECTRONICS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_detail_case_from_env_unset_returns_none() {
+        let _guards = set_case_env(None, None);
+        let result = DetailCase::from_env();
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_detail_case_from_env_sector_title_defaults_industry_upper() {
+        let _guards = set_case_env(Some("title"), None);
+        let result = DetailCase::from_env();
+        assert_eq!(
+            result.unwrap(),
+            Some(DetailCase {
+                sector: TextCase::Title,
+                industry: TextCase::Upper,
+            })
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_detail_case_from_env_rejects_unknown_value() {
+        let _guards = set_case_env(None, Some("lower"));
+        let result = DetailCase::from_env();
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("INDUSTRY_CASE"), "unexpected error: {error}");
+    }
+
+    #[test]
+    fn test_is_younger_than_within_and_outside_window() {
+        let max_age = std::time::Duration::from_secs(3_600);
+        assert!(is_younger_than(1_000, 1_000 + 3_599, max_age));
+        assert!(!is_younger_than(1_000, 1_000 + 3_600, max_age));
+        assert!(!is_younger_than(1_000, 1_000 + 86_400, max_age));
+    }
+
+    #[test]
+    fn test_is_younger_than_treats_future_write_as_stale() {
+        let max_age = std::time::Duration::from_secs(3_600);
+        assert!(!is_younger_than(2_000, 1_000, max_age));
+    }
+
+    #[tokio::test]
+    async fn test_upload_details_csv_stage_failure_reports_live_csv_unchanged() {
+        let state = crate::data::state::unreachable_s3_state();
+        // Skip the bucket check so the upload fails at the staging put.
+        state.mark_s3_ok();
+
+        let error = upload_details_csv(&state).await.unwrap_err();
+        assert!(
+            error.contains(
+                "Failed to stage equity details CSV at data/equity/details/details.csv.staging"
+            ),
+            "{error}"
+        );
+        assert!(
+            error.contains("live CSV data/equity/details/details.csv is unchanged"),
+            "{error}"
+        );
+    }
 }
@@ -23,7 +23,16 @@ use tracing::info;
 /// equity_allocations, equity_orders, equity_portfolio_snapshots, model_runs, and
 /// equity_reconciliation_events. Rows are not deleted here — the unified purge handler
 /// cleans up old data after backup completes.
-pub async fn export_database(state: &State, date: NaiveDate) -> Result<usize, String> {
+///
+/// Quotes, predictions, and reconciliation events are date-scoped and skipped
+/// when the date has no rows. With `empty_schema` they are instead written as
+/// zero-row Parquet files carrying the full column schema, so typed readers
+/// can load every partition without special-casing missing objects.
+pub async fn export_database(
+    state: &State,
+    date: NaiveDate,
+    empty_schema: bool,
+) -> Result<usize, String> {
     let pool = state
         .database
         .pool()
@@ -33,7 +42,7 @@ pub async fn export_database(state: &State, date: NaiveDate) -> Result<usize, St
         .await
         .map_err(|error| format!("Failed to query equity quotes: {}", error))?;
     let quote_count = quotes.len();
-    if quote_count > 0 {
+    if quote_count > 0 || empty_schema {
         let mut quote_dataframe = create_equity_quote_dataframe(&quotes)?;
         write_dataframe_to_s3(
             state,
@@ -49,7 +58,7 @@ pub async fn export_database(state: &State, date: NaiveDate) -> Result<usize, St
         .await
         .map_err(|error| format!("Failed to query equity predictions: {}", error))?;
     let prediction_count = predictions.len();
-    if prediction_count > 0 {
+    if prediction_count > 0 || empty_schema {
         let mut prediction_dataframe = create_equity_prediction_dataframe(&predictions)?;
         write_dataframe_to_s3(
             state,
@@ -137,7 +146,7 @@ pub async fn export_database(state: &State, date: NaiveDate) -> Result<usize, St
         .await
         .map_err(|error| format!("Failed to query equity reconciliation events: {}", error))?;
     let reconciliation_event_count = reconciliation_events.len();
-    if reconciliation_event_count > 0 {
+    if reconciliation_event_count > 0 || empty_schema {
         let mut reconciliation_dataframe =
             create_equity_reconciliation_event_dataframe(&reconciliation_events)?;
         write_dataframe_to_s3(
@@ -173,14 +182,18 @@ async fn write_dataframe_to_s3(
 ) -> Result<(), String> {
     let mut buffer = Vec::new();
     ParquetWriter::new(&mut buffer)
+        .with_compression(state.parquet_compression)
         .finish(dataframe)
         .map_err(|error| format!("Failed to serialize Parquet for {}: {}", key, error))?;
 
+    state.ensure_bucket_accessible().await?;
+
     state
         .s3_client
         .put_object()
         .bucket(&state.bucket_name)
         .key(key)
+        .set_storage_class(state.storage_class.clone())
         .body(ByteStream::from(buffer))
         .send()
         .await
@@ -952,6 +965,24 @@ mod tests {
         assert_eq!(dataframe.column("id").unwrap().dtype(), &DataType::Int64);
     }
 
+    #[test]
+    fn test_empty_prediction_dataframe_keeps_schema_through_parquet() {
+        let mut dataframe = create_equity_prediction_dataframe(&[]).unwrap();
+        let expected_columns = dataframe.get_column_names_owned();
+
+        let mut buffer = Vec::new();
+        ParquetWriter::new(&mut buffer)
+            .finish(&mut dataframe)
+            .unwrap();
+        let read_back = ParquetReader::new(std::io::Cursor::new(buffer))
+            .finish()
+            .unwrap();
+
+        assert_eq!(read_back.height(), 0);
+        assert_eq!(read_back.get_column_names_owned(), expected_columns);
+        assert_eq!(read_back.schema(), dataframe.schema());
+    }
+
     #[test]
     fn test_export_database_returns_error_when_no_database() {
         let runtime = tokio::runtime::Builder::new_current_thread()
@@ -988,7 +1019,7 @@ mod tests {
             assert!(matches!(state.database, DatabaseState::NotConfigured));
 
             let date = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
-            let result = export_database(&state, date).await;
+            let result = export_database(&state, date, false).await;
             assert!(result.is_err());
             assert!(result.unwrap_err().contains("database not connected"));
         });
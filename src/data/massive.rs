@@ -0,0 +1,402 @@
+//! Shared client-side controls for Massive API requests.
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Instant};
+use tracing::{info, warn};
+
+/// Default Massive request rate: at most one request every 250ms. Before the
+/// limiter existed requests were not paced at all, so this also newly slows
+/// the `seed_equity_bars` backfill loop to four days per second; raise
+/// `MASSIVE_REQUESTS_PER_SECOND` where the plan allows more.
+pub const DEFAULT_MASSIVE_REQUESTS_PER_SECOND: f64 = 4.0;
+
+/// Default number of attempts for one Massive request, including the first.
+pub const DEFAULT_MASSIVE_MAX_ATTEMPTS: u32 = 3;
+
+/// Default consecutive Massive failures that open the circuit breaker.
+pub const DEFAULT_MASSIVE_BREAKER_FAILURES: u32 = 5;
+
+/// Default time the circuit breaker stays open before letting requests through.
+pub const DEFAULT_MASSIVE_BREAKER_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Longest `Retry-After` honored, so a misbehaving upstream cannot stall a
+/// sync indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Token-bucket limiter for Massive requests.
+///
+/// Clones share one bucket, so every sync, seed, and gap backfill issued
+/// through the same `State` draws from a single global budget rather than
+/// pacing itself independently. The bucket holds one token, which spaces
+/// requests evenly at the configured rate without allowing bursts.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Returns an error unless `requests_per_second` is finite and positive.
+    pub fn new(requests_per_second: f64) -> Result<Self, String> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(format!(
+                "Massive request rate must be a positive number, got {requests_per_second}"
+            ));
+        }
+        Ok(Self {
+            requests_per_second,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: 1.0,
+                last_refill: Instant::now(),
+            })),
+        })
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Waits until a request may be sent, then consumes one token.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().await;
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(1.0);
+                bucket.last_refill = now;
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.requests_per_second)
+            };
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Circuit breaker for Massive requests.
+///
+/// Opens after `failure_threshold` consecutive failed requests (transport
+/// errors, or 429/5xx once retries are exhausted) and rejects requests
+/// without sending them until `cooldown` has passed. It is then half-open:
+/// requests go through again, a success closes it, and another failure
+/// reopens it for a full cooldown. Clones share one breaker, like
+/// [`RateLimiter`].
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    circuit: Arc<std::sync::Mutex<Circuit>>,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Returns an error unless `failure_threshold` is positive.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Result<Self, String> {
+        if failure_threshold == 0 {
+            return Err("Massive circuit breaker threshold must be positive".to_string());
+        }
+        Ok(Self {
+            failure_threshold,
+            cooldown,
+            circuit: Arc::new(std::sync::Mutex::new(Circuit::default())),
+        })
+    }
+
+    pub fn failure_threshold(&self) -> u32 {
+        self.failure_threshold
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// Returns an error while the breaker is open and the cooldown has not
+    /// passed, so the caller fails fast instead of sending the request.
+    pub fn check(&self) -> Result<(), String> {
+        let circuit = self.circuit.lock().expect("circuit breaker lock poisoned");
+        match circuit.opened_at {
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => {
+                let remaining = self.cooldown - opened_at.elapsed();
+                Err(format!(
+                    "Massive circuit breaker is open after {} consecutive failures; retry in {}s",
+                    circuit.consecutive_failures,
+                    remaining.as_secs()
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the outcome of a request that got a response. 429 and 5xx
+    /// count as failures; any other status closes the breaker.
+    pub fn record_status(&self, status: StatusCode) {
+        if is_retryable(status) {
+            self.record_failure();
+        } else {
+            self.record_success();
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut circuit = self.circuit.lock().expect("circuit breaker lock poisoned");
+        if circuit.opened_at.is_some() {
+            info!("Massive circuit breaker closed");
+        }
+        *circuit = Circuit::default();
+    }
+
+    pub fn record_failure(&self) {
+        let mut circuit = self.circuit.lock().expect("circuit breaker lock poisoned");
+        circuit.consecutive_failures += 1;
+        if circuit.consecutive_failures >= self.failure_threshold {
+            warn!(
+                consecutive_failures = circuit.consecutive_failures,
+                cooldown_seconds = self.cooldown.as_secs(),
+                "Massive circuit breaker opened"
+            );
+            circuit.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Sends a Massive request, retrying 429 and 5xx responses as well as
+/// connect and timeout errors.
+///
+/// This is the only retry layer for Massive fetches. Each attempt first draws
+/// a token from `rate_limiter`. Between attempts it waits for the
+/// `Retry-After` seconds when the response carries one, and otherwise backs
+/// off exponentially from one second. After `max_attempts` the last response
+/// or error is returned as-is so the caller's status handling reports it.
+/// Other transport errors are returned immediately.
+pub async fn send_with_retry(
+    rate_limiter: &RateLimiter,
+    request: RequestBuilder,
+    max_attempts: u32,
+) -> Result<Response, reqwest::Error> {
+    let mut attempt = 1;
+    loop {
+        let attempt_request = request
+            .try_clone()
+            .expect("Massive requests have no streaming body");
+        rate_limiter.acquire().await;
+        let response = match attempt_request.send().await {
+            Ok(response) => response,
+            Err(error) if attempt < max_attempts && (error.is_connect() || error.is_timeout()) => {
+                let delay = backoff(attempt);
+                warn!(
+                    error = %error.without_url(),
+                    attempt = attempt,
+                    max_attempts = max_attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    "Massive request failed, retrying"
+                );
+                sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        let status = response.status();
+        if attempt >= max_attempts || !is_retryable(status) {
+            return Ok(response);
+        }
+        let delay = retry_after(response.headers()).unwrap_or_else(|| backoff(attempt));
+        warn!(
+            status = %status,
+            attempt = attempt,
+            max_attempts = max_attempts,
+            delay_ms = delay.as_millis() as u64,
+            "Massive request failed, retrying"
+        );
+        sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Reads `Retry-After` as whole seconds, capped at [`MAX_RETRY_AFTER`]. The
+/// HTTP-date form is ignored and falls back to the exponential backoff.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Delay after the given failed attempt: 1s, 2s, 4s, ...
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << (attempt - 1).min(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        backoff, is_retryable, retry_after, send_with_retry, CircuitBreaker, RateLimiter,
+        MAX_RETRY_AFTER,
+    };
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_rate_limiter_rejects_non_positive_rate() {
+        assert!(RateLimiter::new(0.0).is_err());
+        assert!(RateLimiter::new(-1.0).is_err());
+        assert!(RateLimiter::new(f64::NAN).is_err());
+        assert!(RateLimiter::new(f64::INFINITY).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_first_request_is_immediate() {
+        let limiter = RateLimiter::new(1.0).unwrap();
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_paces_requests_to_configured_rate() {
+        let limiter = RateLimiter::new(20.0).unwrap();
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+        // One immediate token, then four more at 50ms intervals.
+        assert!(
+            start.elapsed() >= Duration::from_millis(190),
+            "Expected at least ~200ms, got {:?}",
+            start.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_clones_share_one_budget() {
+        let limiter = RateLimiter::new(20.0).unwrap();
+        let other = limiter.clone();
+        let start = Instant::now();
+        let first = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                for _ in 0..2 {
+                    limiter.acquire().await;
+                }
+            }
+        });
+        let second = tokio::spawn(async move {
+            for _ in 0..2 {
+                other.acquire().await;
+            }
+        });
+        first.await.unwrap();
+        second.await.unwrap();
+        assert!(
+            start.elapsed() >= Duration::from_millis(140),
+            "Expected shared pacing of ~150ms, got {:?}",
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_is_retryable_covers_throttling_and_server_errors() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(StatusCode::OK));
+        assert!(!is_retryable(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_retry_after_reads_seconds_and_caps_long_waits() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("86400"));
+        assert_eq!(retry_after(&headers), Some(MAX_RETRY_AFTER));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_backoff_doubles_per_attempt() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_retries_connect_errors_then_returns_them() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let request = reqwest::Client::new().get(format!("http://{address}/"));
+        let limiter = RateLimiter::new(1_000.0).unwrap();
+
+        let start = Instant::now();
+        let error = send_with_retry(&limiter, request, 2).await.unwrap_err();
+
+        assert!(error.is_connect());
+        assert!(start.elapsed() >= backoff(1));
+    }
+
+    #[test]
+    fn test_circuit_breaker_rejects_zero_threshold() {
+        assert!(CircuitBreaker::new(0, Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_circuit_breaker_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60)).unwrap();
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+        breaker.record_status(StatusCode::SERVICE_UNAVAILABLE);
+        let error = breaker.check().unwrap_err();
+        assert!(error.contains("2 consecutive failures"), "{error}");
+    }
+
+    #[test]
+    fn test_circuit_breaker_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60)).unwrap();
+        breaker.record_failure();
+        breaker.record_status(StatusCode::NOT_FOUND);
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO).unwrap();
+        breaker.record_failure();
+        // A zero cooldown has already passed, so the next request is let through.
+        assert!(breaker.check().is_ok());
+        breaker.record_success();
+        assert!(breaker.check().is_ok());
+    }
+}
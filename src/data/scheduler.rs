@@ -3,7 +3,7 @@ use crate::common::events::{
     EventType, CONSUMER_DATA_DATABASE_BACKUP, CONSUMER_DATA_DATABASE_EXPORT,
     CONSUMER_DATA_DATABASE_PURGE, CONSUMER_DATA_EQUITY_BARS_SYNC,
 };
-use crate::data::equity_bars::fetch_and_store_equity_bars;
+use crate::data::equity_bars::{equity_bars_key, fetch_and_store_equity_bars};
 use crate::data::equity_details;
 use crate::data::export;
 use crate::data::market_calendar;
@@ -23,9 +23,6 @@ const SYNC_HOUR: u32 = 1;
 const SYNC_MINUTE: u32 = 0;
 const SYNC_DEDUP_TTL_SECS: u64 = 300;
 
-/// Maximum number of retry attempts for a single Massive API fetch.
-const FETCH_MAX_RETRIES: u32 = 3;
-
 /// Number of calendar days to look back for gap detection during self-healing sync.
 const GAP_DETECTION_LOOKBACK_DAYS: i64 = 90;
 
@@ -104,6 +101,16 @@ fn export_date_from_payload(payload: &serde_json::Value) -> NaiveDate {
         .unwrap_or_else(|| Utc::now().date_naive())
 }
 
+/// Reads the optional `empty_schema` flag from a database export payload.
+///
+/// Defaults to `false`, which skips date-scoped tables that have no rows.
+fn empty_schema_from_payload(payload: &serde_json::Value) -> bool {
+    payload
+        .get("empty_schema")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
 /// Spawns the data sync scheduler loops as background tasks.
 ///
 /// Returns join handles that callers must await after cancelling the
@@ -137,38 +144,6 @@ pub fn spawn_sync_scheduler(
     handles
 }
 
-/// Fetches equity bars for a single trading date with exponential-backoff retry.
-///
-/// Retries up to [`FETCH_MAX_RETRIES`] times on transient failures, with
-/// delays of 1s, 2s between attempts (no delay after the final attempt).
-async fn fetch_with_retry(
-    state: &State,
-    trading_date: &TradingDate,
-) -> Result<Option<usize>, String> {
-    let mut last_error = String::new();
-    for attempt in 0..FETCH_MAX_RETRIES {
-        match fetch_and_store_equity_bars(state, trading_date).await {
-            Ok(result) => return Ok(result),
-            Err(error) => {
-                last_error = error;
-                if attempt + 1 < FETCH_MAX_RETRIES {
-                    let backoff = Duration::from_secs(1 << attempt);
-                    warn!(
-                        attempt = attempt + 1,
-                        max = FETCH_MAX_RETRIES,
-                        backoff_seconds = backoff.as_secs(),
-                        date = %trading_date.as_naive_date(),
-                        error = %last_error,
-                        "Equity bar fetch failed, retrying"
-                    );
-                    sleep(backoff).await;
-                }
-            }
-        }
-    }
-    Err(last_error)
-}
-
 /// Returns expected trading days that are missing from the covered set, excluding
 /// any dates in the `exclude` list (typically today and the just-synced primary date).
 fn detect_coverage_gaps(
@@ -184,23 +159,54 @@ fn detect_coverage_gaps(
         .collect()
 }
 
+/// Result of one self-healing equity bar sync run.
+struct EquityBarSyncOutcome {
+    /// The primary trading date synced (the prior trading day).
+    trading_date: NaiveDate,
+    /// Bars stored for the primary date, or `None` when Massive had no data.
+    primary_bars: Option<usize>,
+    /// Bars stored across the primary date and any backfilled gaps, or `None`
+    /// when nothing was available and gap detection did not run.
+    total_bars: Option<usize>,
+}
+
+impl EquityBarSyncOutcome {
+    /// Payload for `equity_bars_sync_completed`. `bar_count` is kept for
+    /// existing consumers; `s3_key` is `null` when the primary date had no data.
+    fn completed_payload(&self) -> serde_json::Value {
+        let bar_count = self.total_bars.unwrap_or(0);
+        serde_json::json!({
+            "bar_count": bar_count,
+            "date": self.trading_date.format("%Y-%m-%d").to_string(),
+            "s3_key": self.primary_bars.map(|_| equity_bars_key(self.trading_date)),
+            "status": if bar_count > 0 { "ok" } else { "no_content" },
+        })
+    }
+}
+
 /// Self-healing equity bar sync: fetches yesterday's data, then detects and
 /// backfills any gaps in the lookback window.
-async fn run_equity_bar_sync(state: &State) -> Result<Option<usize>, String> {
+async fn run_equity_bar_sync(state: &State) -> Result<EquityBarSyncOutcome, String> {
     let trading_date = sync_date_for(Utc::now());
     info!(
         "Starting equity bar sync for {}",
         trading_date.as_naive_date().format("%Y-%m-%d")
     );
 
-    // Sync the primary target date first (yesterday's trading day).
-    let primary_count = fetch_with_retry(state, &trading_date).await?;
+    // Sync the primary target date first (yesterday's trading day). Retries
+    // happen once, inside the Massive client, so a failed day is not refetched.
+    let primary_count = fetch_and_store_equity_bars(state, &trading_date).await?;
     let mut total_bars = primary_count.unwrap_or(0);
+    let outcome = |total_bars: Option<usize>| EquityBarSyncOutcome {
+        trading_date: trading_date.as_naive_date(),
+        primary_bars: primary_count,
+        total_bars,
+    };
 
     // Self-healing: detect and backfill gaps in the lookback window.
     let pool = match state.database.pool() {
         Some(pool) => pool,
-        None => return Ok(primary_count),
+        None => return Ok(outcome(primary_count)),
     };
 
     let today = Utc::now().with_timezone(&Eastern).date_naive();
@@ -212,7 +218,7 @@ async fn run_equity_bar_sync(state: &State) -> Result<Option<usize>, String> {
             Ok(dates) => dates,
             Err(error) => {
                 warn!(error = %error, "Gap detection query failed, skipping backfill this run");
-                return Ok(Some(total_bars));
+                return Ok(outcome(Some(total_bars)));
             }
         };
 
@@ -224,7 +230,7 @@ async fn run_equity_bar_sync(state: &State) -> Result<Option<usize>, String> {
 
     if gaps.is_empty() {
         info!("No gaps detected in equity bar coverage");
-        return Ok(Some(total_bars));
+        return Ok(outcome(Some(total_bars)));
     }
 
     info!(
@@ -238,7 +244,7 @@ async fn run_equity_bar_sync(state: &State) -> Result<Option<usize>, String> {
         let Some(gap_trading_date) = TradingDate::from_naive_date(*gap_date) else {
             continue;
         };
-        match fetch_with_retry(state, &gap_trading_date).await {
+        match fetch_and_store_equity_bars(state, &gap_trading_date).await {
             Ok(Some(count)) => {
                 backfilled += 1;
                 total_bars += count;
@@ -270,7 +276,7 @@ async fn run_equity_bar_sync(state: &State) -> Result<Option<usize>, String> {
         "Self-healing sync complete"
     );
 
-    Ok(Some(total_bars))
+    Ok(outcome(Some(total_bars)))
 }
 
 async fn sync_loop(state: State, shutdown_token: CancellationToken) {
@@ -297,7 +303,10 @@ async fn sync_loop(state: State, shutdown_token: CancellationToken) {
             continue;
         }
 
-        match run_equity_bar_sync(&state).await {
+        match run_equity_bar_sync(&state)
+            .await
+            .map(|outcome| outcome.total_bars)
+        {
             Ok(Some(bar_count)) => {
                 info!(rows = bar_count, "Equity bar sync completed");
                 state.mark_synced();
@@ -590,25 +599,18 @@ async fn handle_equity_bars_sync(state: &State, pool: &sqlx::PgPool, event_id: i
     }
 
     match run_equity_bar_sync(state).await {
-        Ok(Some(bar_count)) => {
-            info!(rows = bar_count, "Equity bar sync completed");
-            if let Err(error) = emit_event(
-                pool,
-                EventType::EquityBarsSyncCompleted,
-                &serde_json::json!({"bar_count": bar_count}),
-            )
-            .await
-            {
-                warn!(error = %error, "Failed to emit equity_bars_sync_completed");
+        Ok(outcome) => {
+            match outcome.total_bars {
+                Some(bar_count) => {
+                    info!(rows = bar_count, "Equity bar sync completed");
+                    state.mark_synced();
+                }
+                None => info!("No equity bar data available for sync"),
             }
-            state.mark_synced();
-        }
-        Ok(None) => {
-            info!("No equity bar data available for sync");
             if let Err(error) = emit_event(
                 pool,
                 EventType::EquityBarsSyncCompleted,
-                &serde_json::json!({"bar_count": 0}),
+                &outcome.completed_payload(),
             )
             .await
             {
@@ -645,9 +647,15 @@ async fn handle_equity_bars_sync(state: &State, pool: &sqlx::PgPool, event_id: i
 /// Re-seeds equity details from the compile-time embedded CSV.
 ///
 /// Uses `ON CONFLICT DO UPDATE` so sector/industry changes propagate.
-/// Also uploads the CSV to S3 to keep the durable store in sync.
+/// Also uploads the CSV to S3 to keep the durable store in sync, with sector
+/// and industry in the configured [`equity_details::DetailCase`].
+///
+/// When `EQUITY_DETAILS_SKIP_IF_FRESH_SECONDS` is set and the live CSV is
+/// younger than that, only the S3 upload is skipped. PostgreSQL is always
+/// re-seeded so a deploy with an updated embedded CSV is not held back by a
+/// recently written S3 copy.
 async fn run_equity_details_sync(state: &State, pool: &sqlx::PgPool) {
-    let details = match equity_details::parse_embedded_equity_details() {
+    let details = match equity_details::parse_embedded_equity_details(&state.ticker_denylist) {
         Ok(details) => details,
         Err(error) => {
             warn!(error = %error, "Failed to parse embedded equity details");
@@ -660,20 +668,18 @@ async fn run_equity_details_sync(state: &State, pool: &sqlx::PgPool) {
         Err(error) => warn!(error = %error, "Failed to refresh equity details in PostgreSQL"),
     }
 
-    let csv_bytes = equity_details::embedded_csv().as_bytes();
-    let key = "data/equity/details/details.csv";
-    if let Err(error) = state
-        .s3_client
-        .put_object()
-        .bucket(&state.bucket_name)
-        .key(key)
-        .body(ByteStream::from(csv_bytes.to_vec()))
-        .send()
-        .await
-    {
+    if let Some(max_age) = state.equity_details_skip_if_fresh {
+        if equity_details::details_csv_is_fresh(state, max_age).await {
+            info!(
+                max_age_seconds = max_age.as_secs(),
+                "Equity details CSV already fresh, skipping upload"
+            );
+            return;
+        }
+    }
+
+    if let Err(error) = equity_details::upload_details_csv(state).await {
         warn!(error = %error, "Failed to upload equity details CSV to S3");
-    } else {
-        info!(key = key, "Uploaded equity details CSV to S3");
     }
 }
 
@@ -694,7 +700,8 @@ async fn handle_database_export(
         warn!(error = %error, "Failed to emit database_export_started");
     }
 
-    match export::export_database(state, export_date).await {
+    let empty_schema = empty_schema_from_payload(payload);
+    match export::export_database(state, export_date, empty_schema).await {
         Ok(count) => {
             info!(rows = count, "Database export completed");
             if let Err(error) = emit_event(
@@ -927,6 +934,9 @@ async fn run_backup_job(state: &State) -> Result<usize, String> {
     Ok(byte_count)
 }
 
+/// `(host, username, port, dbname, password)` parsed from a PostgreSQL URL.
+type PostgresUrlParts = (String, Option<String>, u16, String, Option<String>);
+
 /// Parses a PostgreSQL connection URL into its components.
 ///
 /// Returns `(host, username, port, dbname, password)`.
@@ -935,9 +945,7 @@ async fn run_backup_job(state: &State) -> Result<usize, String> {
 /// by returning `None` for username and password, allowing the caller to
 /// omit `--username` and `PGPASSWORD` so libpq falls back to OS defaults.
 /// Uses `rsplit_once('@')` so passwords containing `@` are parsed correctly.
-fn parse_postgres_url(
-    url: &str,
-) -> Result<(String, Option<String>, u16, String, Option<String>), String> {
+fn parse_postgres_url(url: &str) -> Result<PostgresUrlParts, String> {
     let without_scheme = url
         .strip_prefix("postgres://")
         .or_else(|| url.strip_prefix("postgresql://"))
@@ -979,8 +987,9 @@ fn parse_postgres_url(
 #[cfg(test)]
 mod tests {
     use super::{
-        detect_coverage_gaps, duration_until_next_sync, export_date_from_payload, is_event_stale,
-        listen_loop, parse_postgres_url, prior_trading_day, spawn_sync_scheduler, sync_date_for,
+        detect_coverage_gaps, duration_until_next_sync, empty_schema_from_payload,
+        export_date_from_payload, is_event_stale, listen_loop, parse_postgres_url,
+        prior_trading_day, spawn_sync_scheduler, sync_date_for, EquityBarSyncOutcome,
         EVENT_FRESHNESS_THRESHOLDS, EXPECTED_CRON_JOBS,
     };
     use chrono::{NaiveDate, TimeZone, Utc};
@@ -988,6 +997,42 @@ mod tests {
     use std::time::Duration;
     use tokio_util::sync::CancellationToken;
 
+    #[test]
+    fn test_completed_payload_names_date_partition_and_status() {
+        let outcome = EquityBarSyncOutcome {
+            trading_date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+            primary_bars: Some(120),
+            total_bars: Some(240),
+        };
+        assert_eq!(
+            outcome.completed_payload(),
+            serde_json::json!({
+                "bar_count": 240,
+                "date": "2025-03-14",
+                "s3_key": "data/equity/bars/year=2025/month=03/day=14/data.parquet",
+                "status": "ok",
+            })
+        );
+    }
+
+    #[test]
+    fn test_completed_payload_without_data_is_no_content() {
+        let outcome = EquityBarSyncOutcome {
+            trading_date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+            primary_bars: None,
+            total_bars: None,
+        };
+        assert_eq!(
+            outcome.completed_payload(),
+            serde_json::json!({
+                "bar_count": 0,
+                "date": "2025-03-14",
+                "s3_key": null,
+                "status": "no_content",
+            })
+        );
+    }
+
     #[test]
     fn test_duration_until_next_sync_is_positive() {
         let duration = duration_until_next_sync(Utc::now());
@@ -1140,6 +1185,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_empty_schema_from_payload_defaults_to_false() {
+        assert!(!empty_schema_from_payload(&serde_json::json!({})));
+        assert!(!empty_schema_from_payload(
+            &serde_json::json!({"empty_schema": "yes"})
+        ));
+        assert!(empty_schema_from_payload(
+            &serde_json::json!({"empty_schema": true})
+        ));
+    }
+
     #[test]
     fn test_export_date_from_payload_falls_back_on_invalid_format() {
         let payload = serde_json::json!({"date": "not-a-date"});
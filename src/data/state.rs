@@ -1,10 +1,19 @@
 use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::data::equity_details::DetailCase;
+use crate::data::massive::{
+    CircuitBreaker, RateLimiter, DEFAULT_MASSIVE_BREAKER_COOLDOWN,
+    DEFAULT_MASSIVE_BREAKER_FAILURES, DEFAULT_MASSIVE_MAX_ATTEMPTS,
+    DEFAULT_MASSIVE_REQUESTS_PER_SECOND,
+};
+use crate::data::ticker_denylist::TickerDenylist;
 use crate::domain::market::Ticker;
+use aws_sdk_s3::types::StorageClass;
 use aws_sdk_s3::Client as S3Client;
+use polars::prelude::ParquetCompression;
 use reqwest::Client as HTTPClient;
 use sqlx::PgPool;
 use tokio::sync::RwLock;
@@ -54,6 +63,212 @@ impl AlpacaCredentials {
     }
 }
 
+/// Reads `key` as `f64`, returning `default` when unset. A present-but-unparseable
+/// value is an error so a misconfigured environment fails fast at startup
+/// rather than silently falling back.
+fn env_f64(key: &str, default: f64) -> Result<f64, String> {
+    match std::env::var(key) {
+        Ok(raw) => raw
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("{key} must be a number, got '{raw}'")),
+        Err(std::env::VarError::NotPresent) => Ok(default),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{key} must be valid UTF-8")),
+    }
+}
+
+/// Reads `key` as a whole number of seconds, returning `None` when unset. A
+/// present-but-unparseable value is an error (see [`env_f64`]).
+fn env_optional_seconds(key: &str) -> Result<Option<Duration>, String> {
+    match std::env::var(key) {
+        Ok(raw) => raw
+            .trim()
+            .parse::<u64>()
+            .map(|seconds| Some(Duration::from_secs(seconds)))
+            .map_err(|_| format!("{key} must be a non-negative integer, got '{raw}'")),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{key} must be valid UTF-8")),
+    }
+}
+
+/// Reads `key` as `true` or `false` (case-insensitive), returning `default`
+/// when unset. Any other value is an error (see [`env_f64`]).
+fn env_bool(key: &str, default: bool) -> Result<bool, String> {
+    match std::env::var(key) {
+        Ok(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(format!("{key} must be 'true' or 'false', got '{raw}'")),
+        },
+        Err(std::env::VarError::NotPresent) => Ok(default),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{key} must be valid UTF-8")),
+    }
+}
+
+/// Reads the Massive API key, preferring the file named by
+/// `MASSIVE_API_KEY_FILE` (for mounted Docker/Kubernetes secrets) over the
+/// inline `MASSIVE_API_KEY`. The file contents are trimmed; an unreadable or
+/// empty file is an error rather than a fallback to the inline value.
+fn massive_api_key_from_env() -> Result<String, String> {
+    match std::env::var("MASSIVE_API_KEY_FILE") {
+        Ok(path) => {
+            let key = std::fs::read_to_string(&path)
+                .map_err(|error| {
+                    format!("Failed to read MASSIVE_API_KEY_FILE {}: {}", path, error)
+                })?
+                .trim()
+                .to_string();
+            if key.is_empty() {
+                return Err(format!("MASSIVE_API_KEY_FILE {} is empty", path));
+            }
+            Ok(key)
+        }
+        Err(std::env::VarError::NotPresent) => std::env::var("MASSIVE_API_KEY")
+            .map_err(|_| "MASSIVE_API_KEY environment variable must be set".to_string()),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err("MASSIVE_API_KEY_FILE must be valid UTF-8".to_string())
+        }
+    }
+}
+
+/// Default retries after a failed S3 request.
+const DEFAULT_S3_MAX_RETRIES: u32 = 3;
+
+/// Reads `S3_MAX_RETRIES` (default 3); `0` disables retries.
+fn s3_max_retries_from_env() -> Result<u32, String> {
+    match std::env::var("S3_MAX_RETRIES") {
+        Ok(raw) => raw
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("S3_MAX_RETRIES must be a non-negative integer, got '{raw}'")),
+        Err(std::env::VarError::NotPresent) => Ok(DEFAULT_S3_MAX_RETRIES),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err("S3_MAX_RETRIES must be valid UTF-8".to_string())
+        }
+    }
+}
+
+/// Reads `READ_BUCKET`, falling back to the write bucket when unset or empty.
+fn read_bucket_name_from_env(bucket_name: &str) -> String {
+    std::env::var("READ_BUCKET")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| bucket_name.to_string())
+}
+
+/// Reads `S3_STORAGE_CLASS` (e.g. `STANDARD_IA`), returning `None` when unset
+/// or empty so objects take the bucket default. An unknown class is an error
+/// rather than being sent to S3 as-is.
+fn storage_class_from_env() -> Result<Option<StorageClass>, String> {
+    match std::env::var("S3_STORAGE_CLASS") {
+        Ok(raw) if raw.trim().is_empty() => Ok(None),
+        Ok(raw) => {
+            let value = raw.trim().to_ascii_uppercase();
+            if StorageClass::values().contains(&value.as_str()) {
+                Ok(Some(StorageClass::from(value.as_str())))
+            } else {
+                Err(format!(
+                    "S3_STORAGE_CLASS must be one of {}, got '{raw}'",
+                    StorageClass::values().join(", ")
+                ))
+            }
+        }
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err("S3_STORAGE_CLASS must be valid UTF-8".to_string())
+        }
+    }
+}
+
+/// Reads `PARQUET_COMPRESSION` (`uncompressed`, `snappy`, or `zstd`,
+/// case-insensitive), defaulting to zstd. Any other value is an error.
+fn parquet_compression_from_env() -> Result<ParquetCompression, String> {
+    match std::env::var("PARQUET_COMPRESSION") {
+        Ok(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "uncompressed" => Ok(ParquetCompression::Uncompressed),
+            "snappy" => Ok(ParquetCompression::Snappy),
+            "zstd" => Ok(ParquetCompression::Zstd(None)),
+            _ => Err(format!(
+                "PARQUET_COMPRESSION must be 'uncompressed', 'snappy', or 'zstd', got '{raw}'"
+            )),
+        },
+        Err(std::env::VarError::NotPresent) => Ok(ParquetCompression::Zstd(None)),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err("PARQUET_COMPRESSION must be valid UTF-8".to_string())
+        }
+    }
+}
+
+/// Builds the shared Massive rate limiter from `MASSIVE_REQUESTS_PER_SECOND`.
+fn massive_rate_limiter_from_env() -> Result<RateLimiter, String> {
+    let requests_per_second = env_f64(
+        "MASSIVE_REQUESTS_PER_SECOND",
+        DEFAULT_MASSIVE_REQUESTS_PER_SECOND,
+    )?;
+    RateLimiter::new(requests_per_second)
+        .map_err(|error| format!("MASSIVE_REQUESTS_PER_SECOND is invalid: {error}"))
+}
+
+/// Reads `key` as a positive integer, returning `default` when unset. Zero or
+/// an unparseable value is an error (see [`env_f64`]).
+fn env_positive_u32(key: &str, default: u32) -> Result<u32, String> {
+    match std::env::var(key) {
+        Ok(raw) => match raw.trim().parse::<u32>() {
+            Ok(value) if value > 0 => Ok(value),
+            _ => Err(format!("{key} must be a positive integer, got '{raw}'")),
+        },
+        Err(std::env::VarError::NotPresent) => Ok(default),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{key} must be valid UTF-8")),
+    }
+}
+
+/// Builds the shared Massive circuit breaker from
+/// `MASSIVE_BREAKER_FAILURES` and `MASSIVE_BREAKER_COOLDOWN_SECONDS`.
+fn massive_circuit_breaker_from_env() -> Result<CircuitBreaker, String> {
+    let failure_threshold =
+        env_positive_u32("MASSIVE_BREAKER_FAILURES", DEFAULT_MASSIVE_BREAKER_FAILURES)?;
+    let cooldown = env_optional_seconds("MASSIVE_BREAKER_COOLDOWN_SECONDS")?
+        .unwrap_or(DEFAULT_MASSIVE_BREAKER_COOLDOWN);
+    CircuitBreaker::new(failure_threshold, cooldown)
+}
+
+/// Default total timeout for one Massive HTTP request.
+const DEFAULT_MASSIVE_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Builds the Massive HTTP client from `MASSIVE_HTTP_TIMEOUT_SECONDS`
+/// (default 10), `MASSIVE_CONNECT_TIMEOUT_SECONDS`, and
+/// `MASSIVE_POOL_MAX_IDLE_PER_HOST`. Unset optional values keep reqwest's
+/// defaults; a present-but-invalid value is an error (see [`env_f64`]).
+fn build_http_client() -> Result<HTTPClient, String> {
+    let timeout = env_optional_seconds("MASSIVE_HTTP_TIMEOUT_SECONDS")?
+        .unwrap_or(DEFAULT_MASSIVE_HTTP_TIMEOUT);
+    let mut builder = HTTPClient::builder().timeout(timeout);
+    if let Some(connect_timeout) = env_optional_seconds("MASSIVE_CONNECT_TIMEOUT_SECONDS")? {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    match std::env::var("MASSIVE_POOL_MAX_IDLE_PER_HOST") {
+        Ok(raw) => {
+            let max_idle = raw.trim().parse::<usize>().map_err(|_| {
+                format!(
+                    "MASSIVE_POOL_MAX_IDLE_PER_HOST must be a non-negative integer, got '{raw}'"
+                )
+            })?;
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        Err(std::env::VarError::NotPresent) => {}
+        Err(std::env::VarError::NotUnicode(_)) => {
+            return Err("MASSIVE_POOL_MAX_IDLE_PER_HOST must be valid UTF-8".to_string())
+        }
+    }
+    debug!(timeout_seconds = timeout.as_secs(), "Creating HTTP client");
+    builder
+        .build()
+        .map_err(|error| format!("Failed to create HTTP client: {error}"))
+}
+
+/// How long a successful bucket check is trusted before writes check again.
+const BUCKET_CHECK_TTL_SECONDS: u64 = 3_600;
+
 /// Database connection state.
 ///
 /// Encodes three distinct states:
@@ -94,22 +309,152 @@ pub struct State {
     pub massive: MassiveSecrets,
     pub s3_client: S3Client,
     pub bucket_name: String,
+    /// Bucket read by the S3 seed source, from `READ_BUCKET`. Defaults to
+    /// `bucket_name`; writes always go to `bucket_name`.
+    pub read_bucket_name: String,
     pub last_s3_ok_epoch: Arc<AtomicU64>,
     pub last_sync_epoch: Arc<AtomicU64>,
     pub database: DatabaseState,
     pub alpaca_credentials: Option<AlpacaCredentials>,
     pub active_symbols: Arc<RwLock<HashSet<Ticker>>>,
+    pub detail_case: Option<DetailCase>,
+    pub massive_rate_limiter: RateLimiter,
+    /// Attempts per Massive request before a 429 or 5xx is returned.
+    pub massive_max_attempts: u32,
+    /// Fails Massive requests fast after repeated upstream failures.
+    pub massive_circuit_breaker: CircuitBreaker,
+    /// Skip the equity details CSV upload while the live CSV is younger than
+    /// this. PostgreSQL is re-seeded regardless.
+    pub equity_details_skip_if_fresh: Option<Duration>,
+    /// Tickers dropped at ingest, from `TICKER_DENYLIST_FILE`.
+    pub ticker_denylist: TickerDenylist,
+    /// Reject a day of equity bars when any bar is timestamped in the future.
+    pub reject_future_timestamps: bool,
+    /// Let an empty day replace an existing equity-bars partition.
+    pub allow_empty_overwrite: bool,
+    /// Storage class for Parquet writes, from `S3_STORAGE_CLASS`. `None`
+    /// leaves the bucket default.
+    pub storage_class: Option<StorageClass>,
+    /// Codec for Parquet writes, from `PARQUET_COMPRESSION`.
+    pub parquet_compression: ParquetCompression,
+}
+
+/// Environment configuration shared by [`State::from_env`] and
+/// [`State::with_pool`], which differ only in how they obtain the S3 client
+/// and database.
+struct DataConfig {
+    http_client: HTTPClient,
+    massive: MassiveSecrets,
+    s3_max_retries: u32,
+    bucket_name: String,
+    read_bucket_name: String,
+    alpaca_credentials: Option<AlpacaCredentials>,
+    detail_case: Option<DetailCase>,
+    massive_rate_limiter: RateLimiter,
+    massive_max_attempts: u32,
+    massive_circuit_breaker: CircuitBreaker,
+    equity_details_skip_if_fresh: Option<Duration>,
+    ticker_denylist: TickerDenylist,
+    reject_future_timestamps: bool,
+    allow_empty_overwrite: bool,
+    storage_class: Option<StorageClass>,
+    parquet_compression: ParquetCompression,
+}
+
+impl DataConfig {
+    /// Reads every data setting from the environment and logs the resolved
+    /// values once. Panics on a missing required variable or an invalid
+    /// value so a misconfigured deployment fails at startup.
+    fn from_env() -> Self {
+        let config = Self::try_from_env().unwrap_or_else(|error| panic!("{}", error));
+        // The API key is never logged.
+        info!(
+            bucket = config.bucket_name,
+            read_bucket = config.read_bucket_name,
+            s3_max_retries = config.s3_max_retries,
+            massive_url = config.massive.base,
+            massive_requests_per_second = config.massive_rate_limiter.requests_per_second(),
+            massive_max_attempts = config.massive_max_attempts,
+            massive_breaker_failures = config.massive_circuit_breaker.failure_threshold(),
+            massive_breaker_cooldown_seconds = config.massive_circuit_breaker.cooldown().as_secs(),
+            alpaca_feed = config.alpaca_credentials.as_ref().map(AlpacaCredentials::feed),
+            detail_case = ?config.detail_case,
+            equity_details_skip_if_fresh_seconds =
+                config.equity_details_skip_if_fresh.map(|max_age| max_age.as_secs()),
+            denylisted_tickers = config.ticker_denylist.len(),
+            reject_future_timestamps = config.reject_future_timestamps,
+            allow_empty_overwrite = config.allow_empty_overwrite,
+            storage_class = config.storage_class.as_ref().map(StorageClass::as_str),
+            parquet_compression = ?config.parquet_compression,
+            "Data configuration resolved"
+        );
+        config
+    }
+
+    fn try_from_env() -> Result<Self, String> {
+        let bucket_name = std::env::var("AWS_S3_BUCKET_NAME")
+            .map_err(|_| "AWS_S3_BUCKET_NAME environment variable must be set".to_string())?;
+        let massive_base_url = std::env::var("MASSIVE_BASE_URL")
+            .map_err(|_| "MASSIVE_BASE_URL environment variable must be set".to_string())?;
+        Ok(Self {
+            http_client: build_http_client()?,
+            massive: MassiveSecrets {
+                base: massive_base_url,
+                key: massive_api_key_from_env()?,
+            },
+            s3_max_retries: s3_max_retries_from_env()?,
+            read_bucket_name: read_bucket_name_from_env(&bucket_name),
+            bucket_name,
+            alpaca_credentials: AlpacaCredentials::from_env(),
+            detail_case: DetailCase::from_env().map_err(|error| error.to_string())?,
+            massive_rate_limiter: massive_rate_limiter_from_env()?,
+            massive_max_attempts: env_positive_u32(
+                "MASSIVE_MAX_ATTEMPTS",
+                DEFAULT_MASSIVE_MAX_ATTEMPTS,
+            )?,
+            massive_circuit_breaker: massive_circuit_breaker_from_env()?,
+            equity_details_skip_if_fresh: env_optional_seconds(
+                "EQUITY_DETAILS_SKIP_IF_FRESH_SECONDS",
+            )?,
+            ticker_denylist: TickerDenylist::from_env()?,
+            reject_future_timestamps: env_bool("REJECT_FUTURE_TIMESTAMPS", false)?,
+            allow_empty_overwrite: env_bool("ALLOW_EMPTY_OVERWRITE", false)?,
+            storage_class: storage_class_from_env()?,
+            parquet_compression: parquet_compression_from_env()?,
+        })
+    }
+
+    fn into_state(self, s3_client: S3Client, database: DatabaseState) -> State {
+        State {
+            http_client: self.http_client,
+            massive: self.massive,
+            s3_client: crate::common::aws::with_max_retries(&s3_client, self.s3_max_retries),
+            bucket_name: self.bucket_name,
+            read_bucket_name: self.read_bucket_name,
+            last_s3_ok_epoch: Arc::new(AtomicU64::new(0)),
+            last_sync_epoch: Arc::new(AtomicU64::new(0)),
+            database,
+            alpaca_credentials: self.alpaca_credentials,
+            active_symbols: Arc::new(RwLock::new(HashSet::new())),
+            detail_case: self.detail_case,
+            massive_rate_limiter: self.massive_rate_limiter,
+            massive_max_attempts: self.massive_max_attempts,
+            massive_circuit_breaker: self.massive_circuit_breaker,
+            equity_details_skip_if_fresh: self.equity_details_skip_if_fresh,
+            ticker_denylist: self.ticker_denylist,
+            reject_future_timestamps: self.reject_future_timestamps,
+            allow_empty_overwrite: self.allow_empty_overwrite,
+            storage_class: self.storage_class,
+            parquet_compression: self.parquet_compression,
+        }
+    }
 }
 
 impl State {
     pub async fn from_env() -> Self {
         info!("Initializing application state from environment");
 
-        debug!("Creating HTTP client with 10s timeout");
-        let http_client = HTTPClient::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .unwrap();
+        let data_config = DataConfig::from_env();
 
         debug!("Loading AWS configuration");
         let config = crate::common::aws::load_config().await;
@@ -120,26 +465,6 @@ impl State {
             .unwrap_or_else(|| "not configured".to_string());
         info!(region = region, "AWS region configured");
 
-        let s3_client = S3Client::new(&config);
-
-        let bucket_name = std::env::var("AWS_S3_BUCKET_NAME")
-            .expect("AWS_S3_BUCKET_NAME environment variable must be set");
-        info!(bucket = bucket_name, "S3 bucket configured");
-
-        let massive_base_url = std::env::var("MASSIVE_BASE_URL")
-            .expect("MASSIVE_BASE_URL environment variable must be set");
-        info!(url = massive_base_url, "Massive API configured");
-
-        let massive_api_key = std::env::var("MASSIVE_API_KEY")
-            .expect("MASSIVE_API_KEY environment variable must be set");
-
-        let alpaca_credentials = AlpacaCredentials::from_env();
-        if let Some(ref credentials) = alpaca_credentials {
-            info!(feed = credentials.feed(), "Alpaca feed configured");
-        } else {
-            info!("Alpaca credentials not configured");
-        }
-
         let database = match std::env::var("DATABASE_URL") {
             Ok(database_url) => {
                 debug!("Connecting to PostgreSQL");
@@ -162,20 +487,7 @@ impl State {
 
         info!("Application state initialized successfully");
 
-        Self {
-            http_client,
-            massive: MassiveSecrets {
-                base: massive_base_url,
-                key: massive_api_key,
-            },
-            s3_client,
-            bucket_name,
-            last_s3_ok_epoch: Arc::new(AtomicU64::new(0)),
-            last_sync_epoch: Arc::new(AtomicU64::new(0)),
-            database,
-            alpaca_credentials,
-            active_symbols: Arc::new(RwLock::new(HashSet::new())),
-        }
+        data_config.into_state(S3Client::new(&config), database)
     }
 
     pub fn new(
@@ -188,12 +500,28 @@ impl State {
             http_client,
             massive,
             s3_client,
+            read_bucket_name: bucket_name.clone(),
             bucket_name,
             last_s3_ok_epoch: Arc::new(AtomicU64::new(0)),
             last_sync_epoch: Arc::new(AtomicU64::new(0)),
             database: DatabaseState::NotConfigured,
             alpaca_credentials: None,
             active_symbols: Arc::new(RwLock::new(HashSet::new())),
+            detail_case: None,
+            massive_rate_limiter: RateLimiter::new(DEFAULT_MASSIVE_REQUESTS_PER_SECOND)
+                .expect("default Massive request rate is valid"),
+            massive_max_attempts: DEFAULT_MASSIVE_MAX_ATTEMPTS,
+            massive_circuit_breaker: CircuitBreaker::new(
+                DEFAULT_MASSIVE_BREAKER_FAILURES,
+                DEFAULT_MASSIVE_BREAKER_COOLDOWN,
+            )
+            .expect("default Massive circuit breaker is valid"),
+            equity_details_skip_if_fresh: None,
+            ticker_denylist: TickerDenylist::default(),
+            reject_future_timestamps: false,
+            allow_empty_overwrite: false,
+            storage_class: None,
+            parquet_compression: ParquetCompression::Zstd(None),
         }
     }
 
@@ -204,32 +532,30 @@ impl State {
     /// configuration (Massive secrets, Alpaca credentials, bucket name) is
     /// read from the environment.
     pub fn with_pool(pool: PgPool, s3_client: S3Client) -> Self {
-        let http_client = HTTPClient::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        let massive_base_url = std::env::var("MASSIVE_BASE_URL")
-            .expect("MASSIVE_BASE_URL environment variable must be set");
-        let massive_api_key = std::env::var("MASSIVE_API_KEY")
-            .expect("MASSIVE_API_KEY environment variable must be set");
-        let bucket_name = std::env::var("AWS_S3_BUCKET_NAME")
-            .expect("AWS_S3_BUCKET_NAME environment variable must be set");
+        DataConfig::from_env().into_state(s3_client, DatabaseState::Connected(pool))
+    }
 
-        Self {
-            http_client,
-            massive: MassiveSecrets {
-                base: massive_base_url,
-                key: massive_api_key,
-            },
-            s3_client,
-            bucket_name,
-            last_s3_ok_epoch: Arc::new(AtomicU64::new(0)),
-            last_sync_epoch: Arc::new(AtomicU64::new(0)),
-            database: DatabaseState::Connected(pool),
-            alpaca_credentials: AlpacaCredentials::from_env(),
-            active_symbols: Arc::new(RwLock::new(HashSet::new())),
+    /// Confirms the bucket exists and is reachable before a write, so the
+    /// first write against a missing or forbidden bucket fails with a clear
+    /// message instead of a generic S3 error. After a success the
+    /// `HeadBucket` call is skipped for [`BUCKET_CHECK_TTL_SECONDS`].
+    pub async fn ensure_bucket_accessible(&self) -> Result<(), String> {
+        if self.s3_ok_recently(BUCKET_CHECK_TTL_SECONDS) {
+            return Ok(());
         }
+        self.s3_client
+            .head_bucket()
+            .bucket(&self.bucket_name)
+            .send()
+            .await
+            .map_err(|error| {
+                format!(
+                    "bucket {} does not exist or is not accessible: {}",
+                    self.bucket_name, error
+                )
+            })?;
+        self.mark_s3_ok();
+        Ok(())
     }
 
     pub fn s3_ok_recently(&self, ttl_secs: u64) -> bool {
@@ -273,11 +599,239 @@ impl State {
     }
 }
 
+/// `State` whose S3 endpoint refuses connections, for unit tests of S3
+/// failure paths. SDK retries are disabled so each call fails at once.
+#[cfg(test)]
+pub(crate) fn unreachable_s3_state() -> State {
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+
+    let config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("us-east-1"))
+        .credentials_provider(Credentials::new(
+            "test-access-key",
+            "test-secret-key",
+            None,
+            None,
+            "tests",
+        ))
+        .endpoint_url("http://127.0.0.1:9")
+        .force_path_style(true)
+        .retry_config(aws_config::retry::RetryConfig::disabled())
+        .build();
+    State::new(
+        reqwest::Client::new(),
+        MassiveSecrets {
+            base: "http://127.0.0.1:1".to_string(),
+            key: "test-api-key".to_string(),
+        },
+        S3Client::from_conf(config),
+        "test-bucket".to_string(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AlpacaCredentials, DatabaseState};
+    use super::{
+        build_http_client, env_bool, env_optional_seconds, env_positive_u32,
+        massive_api_key_from_env, massive_rate_limiter_from_env, parquet_compression_from_env,
+        read_bucket_name_from_env, s3_max_retries_from_env, storage_class_from_env,
+        AlpacaCredentials, DatabaseState,
+    };
+    use crate::common::test_env::{set_env, EnvVarRestoreGuard};
+    use aws_sdk_s3::types::StorageClass;
+    use polars::prelude::ParquetCompression;
     use serial_test::serial;
 
+    #[test]
+    #[serial]
+    fn test_env_optional_seconds_parses_unset_valid_and_invalid() {
+        let key = "EQUITY_DETAILS_SKIP_IF_FRESH_SECONDS";
+        let _guard = EnvVarRestoreGuard::set(key, None);
+        assert_eq!(env_optional_seconds(key).unwrap(), None);
+        set_env(key, Some("3600"));
+        assert_eq!(
+            env_optional_seconds(key).unwrap(),
+            Some(std::time::Duration::from_secs(3600))
+        );
+        set_env(key, Some("one hour"));
+        assert!(env_optional_seconds(key).unwrap_err().contains(key));
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_api_key_from_env_prefers_trimmed_key_file() {
+        let path = std::env::temp_dir().join("fund-massive-api-key-test");
+        std::fs::write(&path, "file-key\n").unwrap();
+        let _key = EnvVarRestoreGuard::set("MASSIVE_API_KEY", Some("inline-key"));
+        let _file = EnvVarRestoreGuard::set("MASSIVE_API_KEY_FILE", path.to_str());
+        let result = massive_api_key_from_env();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap(), "file-key");
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_api_key_from_env_falls_back_to_inline_key() {
+        let _key = EnvVarRestoreGuard::set("MASSIVE_API_KEY", Some("inline-key"));
+        let _file = EnvVarRestoreGuard::set("MASSIVE_API_KEY_FILE", None);
+        assert_eq!(massive_api_key_from_env().unwrap(), "inline-key");
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_api_key_from_env_rejects_missing_key_file() {
+        let path = std::env::temp_dir().join("fund-massive-api-key-missing");
+        let _key = EnvVarRestoreGuard::set("MASSIVE_API_KEY", Some("inline-key"));
+        let _file = EnvVarRestoreGuard::set("MASSIVE_API_KEY_FILE", path.to_str());
+        assert!(massive_api_key_from_env()
+            .unwrap_err()
+            .contains("MASSIVE_API_KEY_FILE"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_read_bucket_name_from_env_defaults_to_write_bucket() {
+        let _guard = EnvVarRestoreGuard::set("READ_BUCKET", None);
+        assert_eq!(read_bucket_name_from_env("write-bucket"), "write-bucket");
+        set_env("READ_BUCKET", Some("read-bucket"));
+        assert_eq!(read_bucket_name_from_env("write-bucket"), "read-bucket");
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_positive_u32_rejects_zero() {
+        let _guard = EnvVarRestoreGuard::set("MASSIVE_MAX_ATTEMPTS", None);
+        assert_eq!(env_positive_u32("MASSIVE_MAX_ATTEMPTS", 3).unwrap(), 3);
+        set_env("MASSIVE_MAX_ATTEMPTS", Some("5"));
+        assert_eq!(env_positive_u32("MASSIVE_MAX_ATTEMPTS", 3).unwrap(), 5);
+        set_env("MASSIVE_MAX_ATTEMPTS", Some("0"));
+        assert!(env_positive_u32("MASSIVE_MAX_ATTEMPTS", 3).is_err());
+    }
+
+    /// Guards the three Massive HTTP client variables and sets them to `values`.
+    fn set_http_client_env(values: [Option<&str>; 3]) -> [EnvVarRestoreGuard; 3] {
+        let [timeout, connect_timeout, pool_max_idle] = values;
+        [
+            EnvVarRestoreGuard::set("MASSIVE_HTTP_TIMEOUT_SECONDS", timeout),
+            EnvVarRestoreGuard::set("MASSIVE_CONNECT_TIMEOUT_SECONDS", connect_timeout),
+            EnvVarRestoreGuard::set("MASSIVE_POOL_MAX_IDLE_PER_HOST", pool_max_idle),
+        ]
+    }
+
+    #[test]
+    #[serial]
+    fn test_build_http_client_defaults_build() {
+        let _guards = set_http_client_env([None, None, None]);
+        assert!(build_http_client().is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_build_http_client_accepts_each_override() {
+        for values in [
+            [Some("30"), None, None],
+            [None, Some("5"), None],
+            [None, None, Some("0")],
+        ] {
+            let _guards = set_http_client_env(values);
+            assert!(build_http_client().is_ok(), "{values:?}");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_build_http_client_rejects_invalid_value() {
+        let _guards = set_http_client_env([None, None, Some("many")]);
+        let error = build_http_client().unwrap_err();
+        assert!(error.contains("MASSIVE_POOL_MAX_IDLE_PER_HOST"), "{error}");
+    }
+
+    #[test]
+    #[serial]
+    fn test_parquet_compression_from_env_defaults_to_zstd() {
+        let _guard = EnvVarRestoreGuard::set("PARQUET_COMPRESSION", None);
+        assert_eq!(
+            parquet_compression_from_env().unwrap(),
+            ParquetCompression::Zstd(None)
+        );
+        set_env("PARQUET_COMPRESSION", Some("Uncompressed"));
+        assert_eq!(
+            parquet_compression_from_env().unwrap(),
+            ParquetCompression::Uncompressed
+        );
+        set_env("PARQUET_COMPRESSION", Some("lz4"));
+        assert!(parquet_compression_from_env().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_s3_max_retries_from_env_parses_unset_zero_and_invalid() {
+        let _guard = EnvVarRestoreGuard::set("S3_MAX_RETRIES", None);
+        assert_eq!(s3_max_retries_from_env().unwrap(), 3);
+        set_env("S3_MAX_RETRIES", Some("0"));
+        assert_eq!(s3_max_retries_from_env().unwrap(), 0);
+        set_env("S3_MAX_RETRIES", Some("-1"));
+        assert!(s3_max_retries_from_env()
+            .unwrap_err()
+            .contains("S3_MAX_RETRIES"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_class_from_env_parses_unset_valid_and_invalid() {
+        let _guard = EnvVarRestoreGuard::set("S3_STORAGE_CLASS", None);
+        assert_eq!(storage_class_from_env().unwrap(), None);
+        set_env("S3_STORAGE_CLASS", Some("standard_ia"));
+        assert_eq!(
+            storage_class_from_env().unwrap(),
+            Some(StorageClass::StandardIa)
+        );
+        set_env("S3_STORAGE_CLASS", Some("COLD"));
+        assert!(storage_class_from_env()
+            .unwrap_err()
+            .contains("INTELLIGENT_TIERING"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_bool_parses_unset_valid_and_invalid() {
+        let key = "REJECT_FUTURE_TIMESTAMPS";
+        let _guard = EnvVarRestoreGuard::set(key, None);
+        assert!(!env_bool(key, false).unwrap());
+        set_env(key, Some("TRUE"));
+        assert!(env_bool(key, false).unwrap());
+        set_env(key, Some("yes"));
+        assert!(env_bool(key, false).unwrap_err().contains(key));
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_rate_limiter_from_env_defaults_to_four_per_second() {
+        let _guard = EnvVarRestoreGuard::set("MASSIVE_REQUESTS_PER_SECOND", None);
+        let limiter = massive_rate_limiter_from_env().unwrap();
+        assert_eq!(limiter.requests_per_second(), 4.0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_rate_limiter_from_env_reads_override() {
+        let _guard = EnvVarRestoreGuard::set("MASSIVE_REQUESTS_PER_SECOND", Some("1.5"));
+        let limiter = massive_rate_limiter_from_env().unwrap();
+        assert_eq!(limiter.requests_per_second(), 1.5);
+    }
+
+    #[test]
+    #[serial]
+    fn test_massive_rate_limiter_from_env_rejects_invalid_values() {
+        let _guard = EnvVarRestoreGuard::save("MASSIVE_REQUESTS_PER_SECOND");
+        for value in ["fast", "0", "-2"] {
+            set_env("MASSIVE_REQUESTS_PER_SECOND", Some(value));
+            let error = massive_rate_limiter_from_env().unwrap_err();
+            assert!(error.contains("MASSIVE_REQUESTS_PER_SECOND"), "{error}");
+        }
+    }
+
     #[test]
     fn test_database_state_not_configured_pool_is_none() {
         assert!(DatabaseState::NotConfigured.pool().is_none());
@@ -580,6 +1134,22 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_ensure_bucket_accessible_reports_unreachable_bucket() {
+        let state = super::unreachable_s3_state();
+
+        let error = state.ensure_bucket_accessible().await.unwrap_err();
+        assert!(
+            error.contains("bucket test-bucket does not exist or is not accessible"),
+            "{error}"
+        );
+        assert!(!state.s3_ok_recently(60));
+
+        // A recent success skips the HeadBucket call entirely.
+        state.mark_s3_ok();
+        assert!(state.ensure_bucket_accessible().await.is_ok());
+    }
+
     #[test]
     fn test_synced_recently_returns_false_when_never_marked() {
         use super::{MassiveSecrets, State};
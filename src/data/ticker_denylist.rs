@@ -0,0 +1,121 @@
+//! Tickers that must never be stored, loaded from `TICKER_DENYLIST_FILE`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::domain::market::Ticker;
+
+/// Set of tickers dropped at ingest before anything is written.
+///
+/// The file lists one ticker per line; blank lines and lines starting with
+/// `#` are ignored. Every entry goes through `Ticker::new`, so the comparison
+/// uses the same normalized form as the ingest paths. Clones share one set.
+#[derive(Clone, Debug, Default)]
+pub struct TickerDenylist {
+    tickers: Arc<HashSet<Ticker>>,
+}
+
+impl TickerDenylist {
+    /// Parses denylist file content. An entry that is not a valid ticker is an
+    /// error so a typo cannot silently let a denied symbol through.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut tickers = HashSet::new();
+        for (index, line) in content.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let ticker = Ticker::new(entry).ok_or_else(|| {
+                format!(
+                    "Invalid ticker '{}' on line {} of ticker denylist",
+                    entry,
+                    index + 1
+                )
+            })?;
+            tickers.insert(ticker);
+        }
+        Ok(Self {
+            tickers: Arc::new(tickers),
+        })
+    }
+
+    /// Reads the file named by `TICKER_DENYLIST_FILE`, returning an empty
+    /// denylist when unset. A set-but-unreadable file is an error so a
+    /// misconfigured environment fails fast at startup.
+    pub fn from_env() -> Result<Self, String> {
+        let path = match std::env::var("TICKER_DENYLIST_FILE") {
+            Ok(path) => path,
+            Err(std::env::VarError::NotPresent) => return Ok(Self::default()),
+            Err(std::env::VarError::NotUnicode(_)) => {
+                return Err("TICKER_DENYLIST_FILE must be valid UTF-8".to_string())
+            }
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|error| format!("Failed to read ticker denylist {}: {}", path, error))?;
+        Self::parse(&content)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tickers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tickers.is_empty()
+    }
+
+    pub fn is_denied(&self, ticker: &Ticker) -> bool {
+        self.tickers.contains(ticker)
+    }
+
+    /// Removes items whose ticker is denied and returns how many were dropped.
+    pub fn retain_allowed<T>(&self, items: &mut Vec<T>, ticker: impl Fn(&T) -> &Ticker) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let before = items.len();
+        items.retain(|item| !self.is_denied(ticker(item)));
+        before - items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TickerDenylist;
+    use crate::domain::market::Ticker;
+
+    #[test]
+    fn test_parse_skips_blank_lines_and_comments() {
+        let denylist = TickerDenylist::parse("# compliance\n\nAAPL\n  msft  \n").unwrap();
+        assert_eq!(denylist.len(), 2);
+        assert!(denylist.is_denied(&Ticker::new("AAPL").unwrap()));
+        assert!(denylist.is_denied(&Ticker::new("MSFT").unwrap()));
+        assert!(!denylist.is_denied(&Ticker::new("GOOG").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_ticker_with_line_number() {
+        let error = TickerDenylist::parse("AAPL\nNOT A TICKER\n").unwrap_err();
+        assert!(error.contains("line 2"), "unexpected error: {error}");
+    }
+
+    #[test]
+    fn test_retain_allowed_drops_denied_and_counts() {
+        let denylist = TickerDenylist::parse("AAPL\n").unwrap();
+        let mut tickers = vec![
+            Ticker::new("AAPL").unwrap(),
+            Ticker::new("MSFT").unwrap(),
+            Ticker::new("AAPL").unwrap(),
+        ];
+        let dropped = denylist.retain_allowed(&mut tickers, |ticker| ticker);
+        assert_eq!(dropped, 2);
+        assert_eq!(tickers, vec![Ticker::new("MSFT").unwrap()]);
+    }
+
+    #[test]
+    fn test_empty_denylist_keeps_everything() {
+        let denylist = TickerDenylist::default();
+        let mut tickers = vec![Ticker::new("AAPL").unwrap()];
+        assert_eq!(denylist.retain_allowed(&mut tickers, |ticker| ticker), 0);
+        assert_eq!(tickers.len(), 1);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::data::errors::Error;
 use crate::data::market_calendar;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use polars::prelude::*;
+use std::collections::HashSet;
 use tracing::{debug, info};
 
 pub use crate::domain::market::{EquityBar, EquityDetail, EquityQuote, Ticker};
@@ -34,12 +35,37 @@ impl TradingDate {
     }
 }
 
+/// Collapse bars sharing a `(ticker, timestamp)` key, keeping the last
+/// occurrence.
+///
+/// Massive's grouped-daily endpoint occasionally returns a ticker more than once
+/// for a date. A single `INSERT ... ON CONFLICT (ticker, timestamp) DO UPDATE`
+/// rejects a repeated conflict target with "ON CONFLICT DO UPDATE command cannot
+/// affect row a second time", which fails the whole 1000-row chunk. Keeping the
+/// last occurrence mirrors the upsert's latest-write semantics, and the S3
+/// Parquet written by [`create_equity_bar_dataframe`] applies the same rule so
+/// both stores hold the same rows.
+pub fn deduplicate_equity_bars(bars: &[EquityBar]) -> Vec<EquityBar> {
+    let mut seen: HashSet<(Ticker, DateTime<Utc>)> = HashSet::with_capacity(bars.len());
+    let mut deduplicated: Vec<EquityBar> = Vec::with_capacity(bars.len());
+    for bar in bars.iter().rev() {
+        if seen.insert((bar.ticker().clone(), bar.timestamp())) {
+            deduplicated.push(bar.clone());
+        }
+    }
+    deduplicated.reverse();
+    deduplicated
+}
+
 pub fn create_equity_bar_dataframe(equity_bars_rows: &[EquityBar]) -> Result<DataFrame, Error> {
     debug!(
         "Creating equity bar DataFrame from {} rows",
         equity_bars_rows.len()
     );
 
+    let equity_bars_rows = deduplicate_equity_bars(equity_bars_rows);
+    let equity_bars_rows = equity_bars_rows.as_slice();
+
     // Ticker values are already normalized (trimmed and uppercased) by Ticker::new.
     let equity_bars_dataframe = df!(
         "ticker" => equity_bars_rows.iter().map(|b| b.ticker().as_str()).collect::<Vec<_>>(),
@@ -70,8 +96,10 @@ pub fn create_equity_bar_dataframe(equity_bars_rows: &[EquityBar]) -> Result<Dat
 
 #[cfg(test)]
 mod tests {
-    use super::{create_equity_bar_dataframe, EquityBar, Ticker, TradingDate};
-    use chrono::{NaiveDate, Utc};
+    use super::{
+        create_equity_bar_dataframe, deduplicate_equity_bars, EquityBar, Ticker, TradingDate,
+    };
+    use chrono::{DateTime, NaiveDate, Utc};
     use polars::prelude::DataType;
 
     fn sample_bar() -> EquityBar {
@@ -89,6 +117,101 @@ mod tests {
         )
     }
 
+    fn bar_for(ticker: &str, timestamp: DateTime<Utc>, close: f64) -> EquityBar {
+        EquityBar::new(
+            Ticker::new(ticker).unwrap(),
+            timestamp,
+            close,
+            close,
+            close,
+            close,
+            1,
+            None,
+            None,
+            timestamp,
+        )
+    }
+
+    #[test]
+    fn test_deduplicate_equity_bars_keeps_last_occurrence_per_key() {
+        let now = Utc::now();
+        let make = |ticker: &str, close: f64| {
+            EquityBar::new(
+                Ticker::new(ticker).unwrap(),
+                now,
+                close,
+                close,
+                close,
+                close,
+                1,
+                None,
+                None,
+                now,
+            )
+        };
+
+        // AAPL appears twice for the same timestamp, as Massive sometimes
+        // returns it. The duplicate would trip "ON CONFLICT DO UPDATE command
+        // cannot affect row a second time" if passed to the upsert unchanged.
+        let bars = vec![
+            make("AAPL", 150.0),
+            make("MSFT", 350.0),
+            make("AAPL", 151.0),
+        ];
+
+        let deduplicated = deduplicate_equity_bars(&bars);
+
+        assert_eq!(deduplicated.len(), 2, "the duplicate ticker must collapse");
+        let aapl = deduplicated
+            .iter()
+            .find(|bar| bar.ticker() == "AAPL")
+            .expect("AAPL must survive deduplication");
+        assert_eq!(
+            aapl.close_price(),
+            151.0,
+            "the last occurrence wins, matching the upsert's latest-write semantics",
+        );
+        assert!(deduplicated.iter().any(|bar| bar.ticker() == "MSFT"));
+    }
+
+    #[test]
+    fn test_deduplicate_equity_bars_distinct_keys_are_untouched() {
+        let bars = vec![sample_bar(), bar_for("MSFT", Utc::now(), 350.0)];
+        let deduplicated = deduplicate_equity_bars(&bars);
+        assert_eq!(deduplicated.len(), bars.len());
+    }
+
+    #[test]
+    fn test_create_equity_bar_dataframe_keeps_last_duplicate() {
+        let timestamp = Utc::now();
+        let bars = vec![
+            bar_for("AAPL", timestamp, 150.0),
+            bar_for("AAPL", timestamp, 151.0),
+        ];
+        let dataframe = create_equity_bar_dataframe(&bars).unwrap();
+        assert_eq!(dataframe.height(), 1);
+        assert_eq!(
+            dataframe
+                .column("close_price")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .get(0),
+            Some(151.0)
+        );
+    }
+
+    #[test]
+    fn test_create_equity_bar_dataframe_keeps_same_ticker_at_different_timestamps() {
+        let timestamp = Utc::now();
+        let bars = vec![
+            bar_for("AAPL", timestamp, 150.0),
+            bar_for("AAPL", timestamp - chrono::Duration::days(1), 149.0),
+        ];
+        let dataframe = create_equity_bar_dataframe(&bars).unwrap();
+        assert_eq!(dataframe.height(), 2);
+    }
+
     #[test]
     fn test_equity_bar_dataframe_matches_pandera_contract() {
         // The S3 parquet schema is the equity_bars_schema pandera contract:
@@ -517,6 +517,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_insert_predictions_rejects_batch_with_one_malformed_ticker() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let prediction = |ticker: &str| {
+                serde_json::json!({
+                    "ticker": ticker,
+                    "timestamp": 1_735_689_600_000_i64,
+                    "quantile_10": -0.01,
+                    "quantile_50": 0.0,
+                    "quantile_90": 0.02,
+                })
+            };
+            let predictions = vec![prediction("AAPL"), prediction("AAPL;DROP")];
+            let error = insert_predictions(&lazy_pool(), &predictions, Uuid::new_v4(), "run-x")
+                .await
+                .unwrap_err();
+            assert!(matches!(error, sqlx::Error::Decode(_)));
+            assert!(error.to_string().contains("AAPL;DROP"), "{error}");
+        });
+    }
+
+    #[test]
+    fn test_prediction_from_json_rejects_empty_ticker() {
+        let prediction = serde_json::json!({
+            "ticker": "",
+            "timestamp": 1_735_689_600_000_i64,
+            "quantile_10": -0.01,
+            "quantile_50": 0.0,
+            "quantile_90": 0.02,
+        });
+        let error = prediction_from_json(&prediction, Uuid::new_v4(), "run-x").unwrap_err();
+        assert!(error.to_string().contains("Invalid ticker"), "{error}");
+    }
+
     #[test]
     fn test_prediction_from_json_accepts_valid_input() {
         let prediction = serde_json::json!({
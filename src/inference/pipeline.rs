@@ -1,6 +1,6 @@
 use std::time::Instant;
 
-use tracing::{error, info, warn};
+use tracing::{error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
 use crate::inference::artifact;
@@ -86,16 +86,26 @@ pub async fn run_predictions(state: &AppState) -> Result<PredictionRun, Pipeline
 
     let correlation_id = Uuid::new_v4();
 
-    let result = run_pipeline_and_persist(state, pool, correlation_id).await;
+    // Every log inside the run carries the correlation id persisted with its
+    // predictions, so one run can be followed from inference into portfolio.
+    let result = run_pipeline_and_persist(state, pool, correlation_id)
+        .instrument(info_span!("prediction_run", correlation_id = %correlation_id))
+        .await;
 
     info!(
+        correlation_id = %correlation_id,
         duration_ms = start.elapsed().as_millis() as u64,
         succeeded = result.is_ok(),
         "Prediction run complete"
     );
 
     if let Err(error) = &result {
-        error!(stage = error.stage(), error = %error.message(), "Prediction pipeline failed");
+        error!(
+            correlation_id = %correlation_id,
+            stage = error.stage(),
+            error = %error.message(),
+            "Prediction pipeline failed"
+        );
         if let Err(emit_error) = crate::common::events::emit_event(
             pool,
             crate::common::events::EventType::EquityPredictionsErrored,
@@ -862,27 +862,28 @@ mod tests {
 
     #[tokio::test]
     async fn test_confirm_fills_zero_fill_price_retries() {
-        let mut fills = Vec::new();
-        // Long leg: first attempt has zero price, second attempt has valid fill.
-        fills.push(OrderFill {
-            alpaca_order_id: "alpaca-long-aapl".to_string(),
-            status: "filled".to_string(),
-            filled_quantity: Some(100.0),
-            fill_price: Some(0.0),
-        });
-        fills.push(OrderFill {
-            alpaca_order_id: "alpaca-long-aapl".to_string(),
-            status: "filled".to_string(),
-            filled_quantity: Some(100.0),
-            fill_price: Some(150.0),
-        });
-        // Short leg: fills immediately.
-        fills.push(OrderFill {
-            alpaca_order_id: "alpaca-short-msft".to_string(),
-            status: "filled".to_string(),
-            filled_quantity: Some(50.0),
-            fill_price: Some(100.0),
-        });
+        let fills = vec![
+            // Long leg: first attempt has zero price, second attempt has valid fill.
+            OrderFill {
+                alpaca_order_id: "alpaca-long-aapl".to_string(),
+                status: "filled".to_string(),
+                filled_quantity: Some(100.0),
+                fill_price: Some(0.0),
+            },
+            OrderFill {
+                alpaca_order_id: "alpaca-long-aapl".to_string(),
+                status: "filled".to_string(),
+                filled_quantity: Some(100.0),
+                fill_price: Some(150.0),
+            },
+            // Short leg: fills immediately.
+            OrderFill {
+                alpaca_order_id: "alpaca-short-msft".to_string(),
+                status: "filled".to_string(),
+                filled_quantity: Some(50.0),
+                fill_price: Some(100.0),
+            },
+        ];
 
         let mock = MockTrading {
             order_fills: std::sync::Mutex::new(fills),
@@ -536,6 +536,12 @@ async fn fetch_market_data(
         return Err(RebalanceError::StalePredictions);
     }
 
+    // Every row shares one correlation id: the prediction run that wrote them.
+    info!(
+        correlation_id = %predictions[0].correlation_id(),
+        rows = predictions.len(),
+        "Loaded predictions for rebalance"
+    );
     let predictions = predictions.to_vec();
 
     let (historical_prices_result, spy_prices_result, equity_details_result) = tokio::join!(
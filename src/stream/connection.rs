@@ -345,7 +345,7 @@ mod tests {
     #[test]
     fn test_backoff_constants() {
         assert!(INITIAL_BACKOFF < MAXIMUM_BACKOFF);
-        assert!(BACKOFF_MULTIPLIER > 1);
+        const { assert!(BACKOFF_MULTIPLIER > 1) };
     }
 
     #[test]
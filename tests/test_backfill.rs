@@ -1,16 +1,21 @@
 mod common;
 
+use aws_sdk_s3::types::StorageClass;
 use chrono::NaiveDate;
 use fund::data::{
-    equity_bars::{seed, SeedSource, SeedTarget},
+    equity_bars::{fetch_and_store_equity_bars, seed, SeedSource, SeedTarget},
+    massive::CircuitBreaker,
     state::{MassiveSecrets, State},
+    ticker_denylist::TickerDenylist,
+    types::TradingDate,
 };
 use mockito::{Matcher, Server};
 use polars::prelude::*;
 use serial_test::serial;
 use std::io::Cursor;
+use std::time::Duration;
 
-use common::{create_test_s3_client, setup_test_bucket, test_bucket_name};
+use common::{create_test_s3_client, put_test_object, setup_test_bucket, test_bucket_name};
 
 const SINGLE_BAR_BODY: &str = r#"{
     "adjusted": true,
@@ -31,6 +36,33 @@ const SINGLE_BAR_BODY: &str = r#"{
     }]
 }"#;
 
+/// Writes a one-row AAPL equity-bars Parquet object to `bucket`/`key`.
+async fn put_bars_parquet(s3: &aws_sdk_s3::Client, bucket: &str, key: &str, timestamp: i64) {
+    let mut dataframe = df!(
+        "ticker" => ["AAPL"],
+        "timestamp" => [timestamp],
+        "open_price" => [100.0],
+        "high_price" => [110.0],
+        "low_price" => [99.0],
+        "close_price" => [105.0],
+        "volume" => [2_000_000i64],
+        "volume_weighted_average_price" => [104.0],
+        "transactions" => [1_000i64],
+    )
+    .unwrap();
+    let mut buffer = Vec::new();
+    ParquetWriter::new(&mut buffer)
+        .finish(&mut dataframe)
+        .unwrap();
+    s3.put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(buffer.into())
+        .send()
+        .await
+        .unwrap();
+}
+
 async fn create_state(massive_base: String, s3_endpoint: &str) -> State {
     let s3_client = create_test_s3_client(s3_endpoint).await;
     State::new(
@@ -122,3 +154,409 @@ async fn test_seed_writes_s3_and_skips_weekends() {
         &DataType::Int64
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_from_s3_fails_day_with_non_parquet_object() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    put_test_object(
+        &s3,
+        "data/equity/bars/year=2025/month=01/day=03/data.parquet",
+        b"ticker,close_price\nAAPL,105.0\n".to_vec(),
+    )
+    .await;
+    let state = create_state("http://127.0.0.1:1".to_string(), &endpoint).await;
+
+    let summary = seed(
+        &state,
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        SeedSource::S3,
+        SeedTarget::PostgreSQL,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(summary.days_failed, 1);
+    assert_eq!(summary.total_bars, 0);
+    let (failed_date, error) = &summary.failures[0];
+    assert_eq!(*failed_date, NaiveDate::from_ymd_opt(2025, 1, 3).unwrap());
+    assert!(
+        error.contains(
+            "not a valid parquet file: data/equity/bars/year=2025/month=01/day=03/data.parquet"
+        ),
+        "{error}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_drops_denylisted_ticker_before_writing() {
+    let (endpoint, s3) = setup_test_bucket().await;
+
+    let body = r#"{
+        "adjusted": true,
+        "queryCount": 2,
+        "request_id": "test",
+        "resultsCount": 2,
+        "status": "OK",
+        "results": [
+            {"T": "AAPL", "c": 105.0, "h": 110.0, "l": 99.0, "n": 1000,
+             "o": 100.0, "t": 1735689600000, "v": 2000000.0, "vw": 104.0},
+            {"T": "MSFT", "c": 405.0, "h": 410.0, "l": 399.0, "n": 1000,
+             "o": 400.0, "t": 1735689600000, "v": 1000000.0, "vw": 404.0}
+        ]
+    }"#;
+    let mut massive_server = Server::new_async().await;
+    massive_server
+        .mock("GET", "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let mut state = create_state(massive_server.url(), &endpoint).await;
+    state.ticker_denylist = TickerDenylist::parse("AAPL\n").unwrap();
+
+    let summary = seed(
+        &state,
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        SeedSource::Massive,
+        SeedTarget::S3,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(summary.total_bars, 1);
+
+    let object = s3
+        .get_object()
+        .bucket(test_bucket_name())
+        .key("data/equity/bars/year=2025/month=01/day=03/data.parquet")
+        .send()
+        .await
+        .expect("Friday partition should exist");
+    let bytes = object.body.collect().await.unwrap().into_bytes();
+    let dataframe = ParquetReader::new(Cursor::new(bytes.to_vec()))
+        .finish()
+        .unwrap();
+
+    let tickers: Vec<&str> = dataframe
+        .column("ticker")
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(tickers, vec!["MSFT"]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_from_s3_reads_read_bucket_and_writes_write_bucket() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    let read_bucket = "test-read-bucket";
+    let _ = s3.create_bucket().bucket(read_bucket).send().await;
+
+    let key = "data/equity/bars/year=2025/month=01/day=03/data.parquet";
+    put_bars_parquet(&s3, read_bucket, key, 1_735_862_400_000).await;
+
+    let mut state = create_state("http://127.0.0.1:1".to_string(), &endpoint).await;
+    state.read_bucket_name = read_bucket.to_string();
+
+    let summary = seed(
+        &state,
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        SeedSource::S3,
+        SeedTarget::S3,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(summary.days_failed, 0);
+    assert_eq!(summary.total_bars, 1);
+    s3.head_object()
+        .bucket(test_bucket_name())
+        .key(key)
+        .send()
+        .await
+        .expect("seeded partition should be written to the write bucket");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_from_s3_skips_missing_partition_and_keeps_present_one() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    // 2025-01-02 has no partition; 2025-01-03 does.
+    put_bars_parquet(
+        &s3,
+        &test_bucket_name(),
+        "data/equity/bars/year=2025/month=01/day=03/data.parquet",
+        1_735_862_400_000,
+    )
+    .await;
+    let state = create_state("http://127.0.0.1:1".to_string(), &endpoint).await;
+
+    let summary = seed(
+        &state,
+        NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        SeedSource::S3,
+        SeedTarget::S3,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(summary.days_processed, 2);
+    assert_eq!(summary.days_failed, 0);
+    assert_eq!(summary.total_bars, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_writes_with_configured_storage_class() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    let mut massive_server = Server::new_async().await;
+    massive_server
+        .mock("GET", "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(SINGLE_BAR_BODY)
+        .create_async()
+        .await;
+    let mut state = create_state(massive_server.url(), &endpoint).await;
+    state.storage_class = Some(StorageClass::StandardIa);
+
+    let date = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    seed(&state, date, date, SeedSource::Massive, SeedTarget::S3)
+        .await
+        .unwrap();
+
+    let head = s3
+        .head_object()
+        .bucket(test_bucket_name())
+        .key("data/equity/bars/year=2025/month=01/day=03/data.parquet")
+        .send()
+        .await
+        .expect("partition should exist");
+    assert_eq!(head.storage_class(), Some(&StorageClass::StandardIa));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_retries_massive_429_then_succeeds() {
+    let (endpoint, _s3) = setup_test_bucket().await;
+    let mut massive_server = Server::new_async().await;
+    let path = "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03";
+    let throttled = massive_server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .expect(2)
+        .create_async()
+        .await;
+    let success = massive_server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(SINGLE_BAR_BODY)
+        .expect(1)
+        .create_async()
+        .await;
+    let state = create_state(massive_server.url(), &endpoint).await;
+
+    let date = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    let summary = seed(&state, date, date, SeedSource::Massive, SeedTarget::S3)
+        .await
+        .unwrap();
+
+    throttled.assert_async().await;
+    success.assert_async().await;
+    assert_eq!(summary.days_failed, 0);
+    assert_eq!(summary.total_bars, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_stops_calling_massive_once_breaker_opens() {
+    let (endpoint, _s3) = setup_test_bucket().await;
+    let mut massive_server = Server::new_async().await;
+    let failing = massive_server
+        .mock(
+            "GET",
+            Matcher::Regex("^/v2/aggs/grouped/locale/us/market/stocks/".to_string()),
+        )
+        .match_query(Matcher::Any)
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+    let mut state = create_state(massive_server.url(), &endpoint).await;
+    state.massive_max_attempts = 1;
+    state.massive_circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60)).unwrap();
+
+    // 2025-01-02 and 2025-01-03 are both trading days.
+    let summary = seed(
+        &state,
+        NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+        SeedSource::Massive,
+        SeedTarget::S3,
+    )
+    .await
+    .unwrap();
+
+    // The first day opens the breaker; the second never reaches Massive.
+    failing.assert_async().await;
+    assert_eq!(summary.days_failed, 2);
+}
+
+/// Grouped-daily body with `count` distinct two-letter tickers.
+fn many_bars_body(count: usize) -> String {
+    let results: Vec<String> = (0..count)
+        .map(|index| {
+            let ticker = format!(
+                "{}{}",
+                (b'A' + (index / 26) as u8) as char,
+                (b'A' + (index % 26) as u8) as char
+            );
+            format!(
+                r#"{{"T":"{ticker}","c":105.0,"h":110.0,"l":99.0,"n":1000,"o":100.0,"t":1735689600000,"v":2000000.0,"vw":104.0}}"#
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"adjusted":true,"queryCount":{count},"request_id":"test","resultsCount":{count},"status":"OK","results":[{}]}}"#,
+        results.join(",")
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_zstd_partition_is_smaller_than_uncompressed_and_round_trips() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    let mut massive_server = Server::new_async().await;
+    massive_server
+        .mock("GET", "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(many_bars_body(200))
+        .create_async()
+        .await;
+    let mut state = create_state(massive_server.url(), &endpoint).await;
+    let date = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    let key = "data/equity/bars/year=2025/month=01/day=03/data.parquet";
+
+    let mut sizes = Vec::new();
+    for compression in [
+        ParquetCompression::Uncompressed,
+        ParquetCompression::Zstd(None),
+    ] {
+        state.parquet_compression = compression;
+        seed(&state, date, date, SeedSource::Massive, SeedTarget::S3)
+            .await
+            .unwrap();
+        let object = s3
+            .get_object()
+            .bucket(test_bucket_name())
+            .key(key)
+            .send()
+            .await
+            .unwrap();
+        let bytes = object.body.collect().await.unwrap().into_bytes();
+        let dataframe = ParquetReader::new(Cursor::new(bytes.to_vec()))
+            .finish()
+            .unwrap();
+        assert_eq!(dataframe.height(), 200);
+        sizes.push(bytes.len());
+    }
+
+    assert!(
+        sizes[1] < sizes[0],
+        "zstd ({}) should be smaller than uncompressed ({})",
+        sizes[1],
+        sizes[0]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_fetch_error_names_upstream_massive_status() {
+    let (endpoint, _s3) = setup_test_bucket().await;
+    let trading_date =
+        TradingDate::from_naive_date(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap()).unwrap();
+
+    for status in [429, 503] {
+        let mut massive_server = Server::new_async().await;
+        massive_server
+            .mock("GET", "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03")
+            .match_query(Matcher::Any)
+            .with_status(status)
+            .create_async()
+            .await;
+        let mut state = create_state(massive_server.url(), &endpoint).await;
+        state.massive_max_attempts = 1;
+
+        let error = fetch_and_store_equity_bars(&state, &trading_date)
+            .await
+            .unwrap_err();
+        assert_eq!(error, format!("Massive returned {status}"));
+    }
+}
+
+async fn seed_single_day(state: &State) -> usize {
+    let date = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    seed(state, date, date, SeedSource::Massive, SeedTarget::S3)
+        .await
+        .unwrap()
+        .days_failed
+}
+
+async fn partition_height(s3: &aws_sdk_s3::Client, key: &str) -> usize {
+    let object = s3
+        .get_object()
+        .bucket(test_bucket_name())
+        .key(key)
+        .send()
+        .await
+        .unwrap();
+    let bytes = object.body.collect().await.unwrap().into_bytes();
+    ParquetReader::new(Cursor::new(bytes.to_vec()))
+        .finish()
+        .unwrap()
+        .height()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_seed_refuses_empty_day_over_existing_partition_unless_allowed() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    let key = "data/equity/bars/year=2025/month=01/day=03/data.parquet";
+    let mut massive_server = Server::new_async().await;
+    massive_server
+        .mock("GET", "/v2/aggs/grouped/locale/us/market/stocks/2025-01-03")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(SINGLE_BAR_BODY)
+        .create_async()
+        .await;
+    let mut state = create_state(massive_server.url(), &endpoint).await;
+
+    // A non-empty day over an existing partition is a normal overwrite.
+    put_bars_parquet(&s3, &test_bucket_name(), key, 1_735_862_400_000).await;
+    assert_eq!(seed_single_day(&state).await, 0);
+    assert_eq!(partition_height(&s3, key).await, 1);
+
+    // Denylisting the only ticker leaves an empty day, which must not replace it.
+    state.ticker_denylist = TickerDenylist::parse("AAPL\n").unwrap();
+    assert_eq!(seed_single_day(&state).await, 1);
+    assert_eq!(partition_height(&s3, key).await, 1);
+
+    state.allow_empty_overwrite = true;
+    assert_eq!(seed_single_day(&state).await, 0);
+    assert_eq!(partition_height(&s3, key).await, 0);
+}
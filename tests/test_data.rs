@@ -141,10 +141,23 @@ fn test_create_equity_bar_dataframe_empty_vec() {
 #[test]
 fn test_create_equity_bar_dataframe_multiple_rows() {
     initialize_test_tracing();
+    // Rows must differ by (ticker, timestamp); exact duplicates collapse.
+    let next_day = chrono::DateTime::from_timestamp(1_234_567_890 + 86_400, 0).unwrap();
     let bars = vec![
         sample_equity_bar(),
-        sample_equity_bar(),
-        sample_equity_bar(),
+        sample_equity_bar_lowercase(),
+        EquityBar::new(
+            Ticker::new("AAPL").unwrap(),
+            next_day,
+            103.0,
+            106.0,
+            102.0,
+            104.0,
+            900_000,
+            Some(104.0),
+            Some(4_000),
+            next_day,
+        ),
     ];
 
     let dataframe = create_equity_bar_dataframe(&bars).unwrap();
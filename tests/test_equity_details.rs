@@ -0,0 +1,94 @@
+mod common;
+
+use fund::data::{
+    equity_details::{details_csv_is_fresh, embedded_csv, upload_details_csv, DETAILS_CSV_KEY},
+    state::{MassiveSecrets, State},
+};
+use serial_test::serial;
+use std::time::Duration;
+
+use common::{create_test_s3_client, put_test_object, setup_test_bucket, test_bucket_name};
+
+const PREVIOUS_CSV: &str = "ticker,sector,industry\nAAPL,Technology,Consumer Electronics\n";
+
+async fn create_state(s3_endpoint: &str) -> State {
+    let s3_client = create_test_s3_client(s3_endpoint).await;
+    State::new(
+        reqwest::Client::new(),
+        MassiveSecrets {
+            base: "http://127.0.0.1:1".to_string(),
+            key: "test-api-key".to_string(),
+        },
+        s3_client,
+        test_bucket_name(),
+    )
+}
+
+async fn read_object(s3: &aws_sdk_s3::Client, key: &str) -> Option<String> {
+    let object = s3
+        .get_object()
+        .bucket(test_bucket_name())
+        .key(key)
+        .send()
+        .await
+        .ok()?;
+    let bytes = object.body.collect().await.unwrap().into_bytes();
+    Some(String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_upload_details_csv_replaces_live_csv_and_removes_staging() {
+    let (endpoint, s3) = setup_test_bucket().await;
+    put_test_object(&s3, DETAILS_CSV_KEY, PREVIOUS_CSV.as_bytes().to_vec()).await;
+    let state = create_state(&endpoint).await;
+
+    upload_details_csv(&state).await.unwrap();
+
+    let live = read_object(&s3, DETAILS_CSV_KEY).await.unwrap();
+    assert_eq!(live, embedded_csv());
+    let staging_key = format!("{}.staging", DETAILS_CSV_KEY);
+    assert!(read_object(&s3, &staging_key).await.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_upload_details_csv_failure_keeps_existing_csv() {
+    let (_endpoint, s3) = setup_test_bucket().await;
+    put_test_object(&s3, DETAILS_CSV_KEY, PREVIOUS_CSV.as_bytes().to_vec()).await;
+    let failing_state = create_state("http://127.0.0.1:9").await;
+
+    let error = upload_details_csv(&failing_state).await.unwrap_err();
+    assert!(error.contains("is unchanged"), "{error}");
+
+    let live = read_object(&s3, DETAILS_CSV_KEY).await.unwrap();
+    assert_eq!(live, PREVIOUS_CSV);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_details_csv_is_fresh_after_upload_within_window() {
+    let (endpoint, _s3) = setup_test_bucket().await;
+    let state = create_state(&endpoint).await;
+
+    assert!(!details_csv_is_fresh(&state, Duration::from_secs(3_600)).await);
+
+    upload_details_csv(&state).await.unwrap();
+
+    assert!(details_csv_is_fresh(&state, Duration::from_secs(3_600)).await);
+    assert!(!details_csv_is_fresh(&state, Duration::from_secs(0)).await);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_upload_details_csv_to_missing_bucket_names_bucket() {
+    let (endpoint, _s3) = setup_test_bucket().await;
+    let mut state = create_state(&endpoint).await;
+    state.bucket_name = "bucket-that-does-not-exist".to_string();
+
+    let error = upload_details_csv(&state).await.unwrap_err();
+    assert!(
+        error.contains("bucket bucket-that-does-not-exist does not exist or is not accessible"),
+        "{error}"
+    );
+}
@@ -2,9 +2,18 @@ mod common;
 
 #[test]
 fn test_embedded_equity_details_csv_parses_successfully() {
-    let result = fund::data::equity_details::parse_embedded_equity_details();
+    let denylist = fund::data::ticker_denylist::TickerDenylist::default();
+    let result = fund::data::equity_details::parse_embedded_equity_details(&denylist);
     assert!(result.is_ok());
     let details = result.unwrap();
     assert!(!details.is_empty());
     assert!(details.iter().any(|d| d.ticker().as_str() == "AAPL"));
 }
+
+#[test]
+fn test_embedded_equity_details_drops_denylisted_ticker() {
+    let denylist = fund::data::ticker_denylist::TickerDenylist::parse("AAPL\n").unwrap();
+    let details = fund::data::equity_details::parse_embedded_equity_details(&denylist).unwrap();
+    assert!(!details.is_empty());
+    assert!(!details.iter().any(|d| d.ticker().as_str() == "AAPL"));
+}